@@ -1,9 +1,18 @@
 // https://godotshaders.com/shader/animated-diamond-pattern/
 
 #import bevy_sprite::mesh2d_vertex_output::VertexOutput
-#import bevy_render::globals::Globals
 
-@group(0) @binding(1) var<uniform> globals: Globals;
+struct Background {
+    time: f32,
+    beat: f32,
+    flash: f32,
+    intensity: f32,
+    light: vec4<f32>,
+    dark: vec4<f32>,
+    accent: vec4<f32>,
+}
+
+@group(2) @binding(0) var<uniform> background: Background;
 
 // ############### GRID
 
@@ -65,8 +74,8 @@ fn fragment(input: VertexOutput) -> @location(0) vec4<f32> {
     let dist = 2.0 * min(min(rep.x, 1.0 - rep.x), min(rep.y, 1.0 - rep.y));
     let squareDist = length((floor(pos) + vec2<f32>(0.5)) - vec2<f32>(5.0));
 
-    var edge = sin(globals.time - squareDist * 0.5) * 0.5 + 0.5;
-    edge = (globals.time - squareDist * 0.5) * 0.5;
+    var edge = sin(background.time - squareDist * 0.5) * 0.5 + 0.5;
+    edge = (background.time - squareDist * 0.5) * 0.5;
     edge = 2.0 * fract(edge * 0.5);
 
     value = fract(dist * 2.0);
@@ -75,8 +84,12 @@ fn fragment(input: VertexOutput) -> @location(0) vec4<f32> {
     value = smoothstep(edge - 0.05, edge, 0.95 * value);
     value += squareDist * 0.1;
 
-    var color = mix(vec4<f32>(0.2, 0.22352, 0.2549019, 1.0), vec4<f32>(0.05, 0.05, 0.05, 1.0), value);
-    color.a = 0.25 * clamp(value, 0.0, 0.8);
+    // Flashes on each beat and fades before the next.
+    let pulse = pow(1.0 - background.beat, 4.0) * background.flash;
+
+    var color = mix(background.light, background.dark, value);
+    color = vec4<f32>(mix(color.rgb, background.accent.rgb, background.intensity * 0.6), 1.0);
+    color.a = 0.25 * clamp(value, 0.0, 0.8) * (1.0 + 0.4 * pulse + background.intensity);
 
     return color;
 }
@@ -4,7 +4,24 @@
 @group(2) @binding(1) var texture_sampler: sampler;
 @group(2) @binding(2) var<uniform> uv_offset: vec2f;
 
+struct Background {
+    time: f32,
+    beat: f32,
+    flash: f32,
+    intensity: f32,
+    light: vec4f,
+    dark: vec4f,
+    accent: vec4f,
+}
+
+@group(2) @binding(3) var<uniform> background: Background;
+
 @fragment
 fn fragment(mesh: VertexOutput) -> @location(0) vec4f {
-    return textureSample(texture, texture_sampler, mesh.uv + uv_offset);
+    let color = textureSample(texture, texture_sampler, mesh.uv + uv_offset);
+
+    // Brightens on each beat and fades before the next.
+    let pulse = pow(1.0 - background.beat, 4.0) * background.flash;
+    let rgb = mix(color.rgb, background.accent.rgb, background.intensity * 0.3);
+    return vec4f(rgb * (1.0 + 0.15 * pulse), color.a);
 }
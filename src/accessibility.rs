@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use bevy_persistent::prelude::*;
+
+use crate::settings::Settings;
+
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, scale_text);
+    }
+}
+
+/// Feature colors, with sets that stay apart for the common kinds of color blindness.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Palette {
+    #[default]
+    Standard,
+    /// For deuteranopia and protanopia.
+    RedGreen,
+    /// For tritanopia.
+    BlueYellow,
+}
+
+impl Palette {
+    pub const ALL: [Self; 3] = [Self::Standard, Self::RedGreen, Self::BlueYellow];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Standard => "STANDARD",
+            Self::RedGreen => "RED-GREEN",
+            Self::BlueYellow => "BLUE-YELLOW",
+        }
+    }
+
+    /// Steps through the palettes, wrapping around.
+    pub fn cycle(&self, step: i32) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|palette| palette == self)
+            .unwrap_or(0);
+        let step = if step == 0 { 1 } else { step };
+        Self::ALL[(index as i32 + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+
+    /// Whether features should also carry an icon, so color isn't all that tells them apart.
+    pub fn icons(&self) -> bool {
+        *self != Self::Standard
+    }
+
+    /// The color of the feature drawn in row `row` of the feature atlas, or `None` for
+    /// features to keep their own.
+    pub fn feature(&self, row: usize) -> Option<Color> {
+        let colors = match self {
+            Self::Standard => return None,
+            // Okabe and Ito's palette, which keeps hues apart without red against green.
+            Self::RedGreen => [
+                hex(0xd55e00),
+                hex(0x56b4e9),
+                hex(0xf0e442),
+                hex(0x009e73),
+                hex(0x009e73),
+                hex(0xcc79a7),
+                hex(0x0072b2),
+                hex(0xe69f00),
+            ],
+            // Reds and blues only, which stay apart without blue against yellow.
+            Self::BlueYellow => [
+                hex(0xe41a1c),
+                hex(0x00bcd4),
+                hex(0xff80ab),
+                hex(0x1e88e5),
+                hex(0x1e88e5),
+                hex(0xb71c1c),
+                hex(0x283593),
+                hex(0xf8bbd0),
+            ],
+        };
+        colors.get(row).map(|color| Color::Srgba(*color))
+    }
+}
+
+fn hex(color: u32) -> Srgba {
+    Srgba::rgb_u8((color >> 16) as u8, (color >> 8) as u8, color as u8)
+}
+
+/// Text whose font size follows the text scale setting, from its size at a scale of 1.
+#[derive(Clone, Copy, Component)]
+#[require(TextFont)]
+pub struct ScaledText(pub f32);
+
+fn scale_text(
+    settings: Res<Persistent<Settings>>,
+    mut text: Query<(Ref<ScaledText>, &mut TextFont)>,
+) {
+    for (scaled, mut font) in text.iter_mut() {
+        if settings.is_changed() || scaled.is_added() {
+            font.font_size = scaled.0 * settings.text_scale;
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::reflect::Typed;
+use bevy_enhanced_input::events::Fired;
+use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
+use bevy_tween::prelude::*;
+use bevy_tween::tween::IntoTarget;
+use dashu::ibig;
+
+use crate::ball::BallComponents;
+use crate::collectables::{POINT_COLOR, Points};
+use crate::feature::Redirector;
+use crate::feature::grid::SlotFeatureOf;
+use crate::input::Back;
+use crate::leaderboard::checkers;
+use crate::save::{PlayerData, PlayerSave};
+use crate::stage::{AdvanceEvent, Stage};
+use crate::state::{self, GameState, Playing, remove_entities};
+use crate::text::text_alpha;
+use crate::touch::Taps;
+use crate::transition::{GoTo, MENU_FADE_SECS, Transition};
+use crate::tween::DespawnTweenFinish;
+use crate::{cabinet, sandbox};
+
+/// Features every player starts with. The rest are unlocked by [`Achievement`]s.
+pub const STARTER_FEATURES: [&str; 3] = ["Bumper", "Dispenser", "MoneyBumper"];
+
+pub struct AchievementPlugin;
+
+impl Plugin for AchievementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Achievements), spawn_achievements)
+            .add_systems(
+                OnExit(GameState::Achievements),
+                remove_entities::<With<AchievementScreen>>,
+            )
+            .add_systems(Update, tap_to_menu.in_set(state::Achievements))
+            .add_observer(back_to_menu);
+
+        if !sandbox::ENABLED {
+            app.add_systems(
+                Update,
+                (check_achievements.in_set(Playing), redirectors_only),
+            );
+        }
+    }
+}
+
+/// Persisted in [`PlayerData`] by variant index, so only ever append new variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Achievement {
+    ClearStage,
+    ReachStage3,
+    ReachStage5,
+    Balls100,
+    Balls500,
+    StagePoints,
+    RedirectorsOnly,
+}
+
+impl Achievement {
+    pub const ALL: [Self; 7] = [
+        Self::ClearStage,
+        Self::ReachStage3,
+        Self::ReachStage5,
+        Self::Balls100,
+        Self::Balls500,
+        Self::StagePoints,
+        Self::RedirectorsOnly,
+    ];
+
+    pub fn desc(&self) -> &'static str {
+        match self {
+            Self::ClearStage => "Clear a stage",
+            Self::ReachStage3 => "Reach stage 3",
+            Self::ReachStage5 => "Reach stage 5",
+            Self::Balls100 => "Have 100 balls alive at once",
+            Self::Balls500 => "Have 500 balls alive at once",
+            Self::StagePoints => "Score 1e12 in one stage",
+            Self::RedirectorsOnly => "Clear a stage using only redirectors",
+        }
+    }
+
+    /// Type names of the features this achievement adds to the pool.
+    pub fn unlocks(&self) -> &'static [&'static str] {
+        match self {
+            Self::ClearStage => &["BingBong", "Lotto"],
+            Self::ReachStage3 => &[
+                "NorthWestRedirector",
+                "NorthEastRedirector",
+                "SouthWestRedirector",
+                "SouthEastRedirector",
+            ],
+            Self::ReachStage5 => &["FieldInverter"],
+            Self::Balls100 => &["Splitter"],
+            Self::Balls500 | Self::StagePoints | Self::RedirectorsOnly => &[],
+        }
+    }
+}
+
+impl PlayerData {
+    pub fn has_achievement(&self, achievement: Achievement) -> bool {
+        self.achievements.contains(&achievement)
+    }
+
+    pub fn is_unlocked<T: Typed>(&self) -> bool {
+        let name = T::type_ident().unwrap();
+        sandbox::ENABLED
+            || STARTER_FEATURES.contains(&name)
+            || self
+                .achievements
+                .iter()
+                .any(|achievement| achievement.unlocks().contains(&name))
+    }
+}
+
+fn unlock(
+    commands: &mut Commands,
+    server: &AssetServer,
+    data: &mut PlayerSave,
+    achievement: Achievement,
+) {
+    if data.has_achievement(achievement) {
+        return;
+    }
+
+    info!("unlocked achievement: {achievement:?}");
+    data.achievements.push(achievement);
+    if let Err(e) = data.persist() {
+        error!("failed to save player data: {e}");
+    }
+
+    let text = commands
+        .spawn((
+            HIGH_RES_LAYER,
+            Text2d::new(format!("ACHIEVEMENT: {}", achievement.desc())),
+            TextFont {
+                font_size: 32.,
+                font: server.load("fonts/cube.ttf"),
+                ..Default::default()
+            },
+            TextColor(POINT_COLOR.into()),
+            Transform::from_xyz(0., crate::RES_HEIGHT / 2. - 60., 900.),
+        ))
+        .id();
+    commands
+        .entity(text)
+        .animation()
+        .insert_tween_here(
+            Duration::from_secs_f32(3.),
+            EaseKind::QuarticIn,
+            text.into_target().with(text_alpha(1., 0.)),
+        )
+        .insert(DespawnTweenFinish);
+}
+
+fn check_achievements(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    stage: Single<&Stage>,
+    points: Res<Points>,
+    balls: Query<(), With<BallComponents>>,
+    mut data: ResMut<PlayerSave>,
+) {
+    let mut earned = Vec::new();
+
+    let stage = stage.level + 1;
+    if stage >= 2 {
+        earned.push(Achievement::ClearStage);
+    }
+    if stage >= 3 {
+        earned.push(Achievement::ReachStage3);
+    }
+    if stage >= 5 {
+        earned.push(Achievement::ReachStage5);
+    }
+
+    let alive = balls.iter().len();
+    if alive >= 100 {
+        earned.push(Achievement::Balls100);
+    }
+    if alive >= 500 {
+        earned.push(Achievement::Balls500);
+    }
+
+    if points.is_changed() && points.get().0 >= ibig!(10).pow(12) {
+        earned.push(Achievement::StagePoints);
+    }
+
+    // Checked through `Deref` first, so the save isn't marked changed every frame.
+    for achievement in earned {
+        if !data.has_achievement(achievement) {
+            unlock(&mut commands, &server, &mut data, achievement);
+        }
+    }
+}
+
+fn redirectors_only(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    mut advances: EventReader<AdvanceEvent>,
+    features: Query<Has<Redirector>, With<SlotFeatureOf>>,
+    mut data: ResMut<PlayerSave>,
+) {
+    if advances.read().last().is_some()
+        && !features.is_empty()
+        && features.iter().all(|redirector| redirector)
+        && !data.has_achievement(Achievement::RedirectorsOnly)
+    {
+        unlock(
+            &mut commands,
+            &server,
+            &mut data,
+            Achievement::RedirectorsOnly,
+        );
+    }
+}
+
+#[derive(Component)]
+struct AchievementScreen;
+
+const SCREENZ: f32 = 800.;
+
+fn spawn_achievements(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    data: Res<PlayerSave>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mats: ResMut<Assets<cabinet::ScrollingTexture>>,
+) {
+    commands.spawn((AchievementScreen, checkers(&server, &mut meshes, &mut mats)));
+
+    commands.spawn((
+        AchievementScreen,
+        Text2d::new("ACHIEVEMENTS"),
+        TextFont {
+            font_size: 54.,
+            font: server.load("fonts/saiba.ttf"),
+            ..Default::default()
+        },
+        HIGH_RES_LAYER,
+        Transform::from_xyz(0., crate::RES_HEIGHT / 3., SCREENZ),
+    ));
+
+    for (i, achievement) in Achievement::ALL.iter().enumerate() {
+        let unlocked = data.has_achievement(*achievement);
+        let row = match achievement.unlocks().len() {
+            0 => achievement.desc().to_string(),
+            1 => format!("{}   +1 feature", achievement.desc()),
+            n => format!("{}   +{n} features", achievement.desc()),
+        };
+
+        commands.spawn((
+            AchievementScreen,
+            Text2d::new(row),
+            TextFont {
+                font_size: 28.,
+                ..Default::default()
+            },
+            TextColor(if unlocked {
+                POINT_COLOR.into()
+            } else {
+                Color::srgb(0.5, 0.5, 0.5)
+            }),
+            HIGH_RES_LAYER,
+            Transform::from_xyz(
+                0.,
+                crate::RES_HEIGHT / 3. - 100. - (i as f32 * 50.),
+                SCREENZ,
+            ),
+        ));
+    }
+}
+
+fn back_to_menu(_: Trigger<Fired<Back>>, mut commands: Commands, state: Res<State<GameState>>) {
+    if *state.get() == GameState::Achievements {
+        commands.send_event(GoTo::new(
+            GameState::Menu,
+            Transition::fade().with_duration(MENU_FADE_SECS),
+        ));
+    }
+}
+
+fn tap_to_menu(mut commands: Commands, mut taps: Taps) {
+    if taps.read().count() > 0 {
+        commands.send_event(GoTo::new(
+            GameState::Menu,
+            Transition::fade().with_duration(MENU_FADE_SECS),
+        ));
+    }
+}
@@ -131,6 +131,10 @@ impl AnimationIndices {
         Self::new(AnimationMode::Repeat, seq)
     }
 
+    pub fn once(seq: impl IntoIterator<Item = usize>) -> Self {
+        Self::new(AnimationMode::Once, seq)
+    }
+
     pub fn once_despawn(seq: impl IntoIterator<Item = usize>) -> Self {
         Self::new(AnimationMode::Despawn, seq)
     }
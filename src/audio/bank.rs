@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_seedling::sample::PitchRange;
+use rand::seq::SliceRandom;
+
+use super::Bus;
+use super::voices::SoundLimit;
+use crate::loading::RonAsset;
+
+/// Named sound cues, loaded from `audio/sounds.bank.ron`.
+///
+/// Gameplay code only refers to cue names, so samples, levels and limits can be
+/// reassigned in the bank without touching Rust.
+#[derive(Asset, TypePath, serde::Deserialize)]
+#[serde(transparent)]
+pub struct SoundBank(HashMap<String, Cue>);
+
+impl RonAsset for SoundBank {
+    const EXTENSIONS: &'static [&'static str] = &["bank.ron"];
+}
+
+impl SoundBank {
+    pub fn get(&self, cue: &str) -> Option<&Cue> {
+        self.0.get(cue)
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Cue {
+    /// One of these is picked at random each time the cue plays.
+    pub samples: Vec<String>,
+    #[serde(default = "Cue::default_bus")]
+    pub bus: Bus,
+    /// Gain in decibels.
+    #[serde(default)]
+    pub volume: f32,
+    /// Playback speed is picked at random from this range.
+    #[serde(default)]
+    pub pitch: Option<(f64, f64)>,
+    #[serde(default)]
+    pub limit: SoundLimit,
+}
+
+impl Cue {
+    fn default_bus() -> Bus {
+        Bus::Sfx
+    }
+
+    pub fn sample(&self) -> Option<&str> {
+        self.samples
+            .choose(&mut rand::thread_rng())
+            .map(String::as_str)
+    }
+
+    pub fn pitch(&self) -> Option<PitchRange> {
+        self.pitch.map(|(low, high)| PitchRange(low..high))
+    }
+}
+
+/// The bank every [`PlaySound`](super::PlaySound) is resolved against.
+#[derive(Resource)]
+pub struct Sounds(pub Handle<SoundBank>);
+
+pub(super) fn load_bank(mut commands: Commands, server: Res<AssetServer>) {
+    commands.insert_resource(Sounds(server.load("audio/sounds.bank.ron")));
+}
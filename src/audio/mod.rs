@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use bevy_persistent::prelude::*;
+use bevy_seedling::prelude::*;
+
+use crate::loading::RonLoader;
+use crate::settings::Settings;
+
+mod bank;
+mod voices;
+
+pub use bank::{Cue, SoundBank, Sounds};
+pub use voices::{PlaySound, SoundLimit};
+
+pub struct AudioPlugin;
+
+impl Plugin for AudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<SoundBank>()
+            .init_asset_loader::<RonLoader<SoundBank>>()
+            .add_event::<PlaySound>()
+            .init_resource::<voices::VoiceLimiter>()
+            .add_systems(Startup, (spawn_pools, bank::load_bank))
+            .add_systems(Update, apply_volumes)
+            .add_systems(PostUpdate, voices::play_sounds);
+    }
+}
+
+/// Gameplay sounds: bonks, bumpers, paddles, scoring and stage changes.
+#[derive(PoolLabel, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SfxPool;
+
+/// Interface feedback, like being denied a purchase.
+#[derive(PoolLabel, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct UiPool;
+
+/// The two music decks, so one track can fade in while the last fades out.
+#[derive(PoolLabel, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct MusicPool;
+
+#[derive(PoolLabel, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct MusicFadePool;
+
+/// Sound cues on the music [`Bus`], played through the music's filters beside the decks.
+#[derive(PoolLabel, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct MusicCuePool;
+
+/// A volume node whose level is set by the player. The master bus is seedling's [`MainBus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, serde::Deserialize)]
+pub enum Bus {
+    Music,
+    Sfx,
+    Ui,
+}
+
+impl Bus {
+    fn volume(&self, settings: &Settings) -> f32 {
+        match self {
+            Self::Music => settings.music_volume,
+            Self::Sfx => settings.sfx_volume,
+            Self::Ui => settings.ui_volume,
+        }
+    }
+}
+
+fn spawn_pools(mut commands: Commands) {
+    commands.spawn((SamplerPool(SfxPool), Bus::Sfx, VolumeNode::default()));
+    commands.spawn((SamplerPool(UiPool), Bus::Ui, VolumeNode::default()));
+}
+
+fn apply_volumes(
+    settings: Res<Persistent<Settings>>,
+    mut buses: Query<(Ref<Bus>, &mut VolumeNode), Without<MainBus>>,
+    mut master: Query<&mut VolumeNode, With<MainBus>>,
+) {
+    for (bus, mut node) in buses.iter_mut() {
+        if settings.is_changed() || bus.is_added() {
+            node.volume = Volume::Linear(bus.volume(&settings));
+        }
+    }
+
+    for mut node in master.iter_mut() {
+        if settings.is_changed() || node.is_added() {
+            node.volume = Volume::Linear(settings.master_volume);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_seedling::prelude::*;
+
+use super::bank::{SoundBank, Sounds};
+use super::{Bus, MusicCuePool, SfxPool, UiPool};
+
+/// Plays a cue from the [`SoundBank`] through the voice limiter.
+///
+/// Requests for the same cue that arrive faster than its [`SoundLimit`] allows are
+/// folded together and played once, louder.
+#[derive(Event, Clone)]
+pub struct PlaySound {
+    cue: &'static str,
+    count: u32,
+}
+
+impl PlaySound {
+    pub fn new(cue: &'static str) -> Self {
+        Self { cue, count: 1 }
+    }
+
+    /// Stands in for `count` simultaneous requests.
+    pub fn times(mut self, count: u32) -> Self {
+        self.count = count;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(default)]
+pub struct SoundLimit {
+    /// Most voices of the cue playing at once.
+    pub voices: usize,
+    /// Shortest time between two starts of the cue, in seconds.
+    pub interval: f64,
+}
+
+impl Default for SoundLimit {
+    fn default() -> Self {
+        Self {
+            voices: 6,
+            interval: 0.03,
+        }
+    }
+}
+
+/// Folded requests are dropped if they can't be played within this many seconds.
+const MAX_LATENCY: f64 = 0.15;
+
+/// Loudest a folded request can get, in decibels above a single one.
+const MAX_FOLD_GAIN: f32 = 9.;
+
+/// A playing voice started by the limiter.
+#[derive(Component)]
+struct Voice(&'static str);
+
+struct Pending {
+    count: u32,
+    since: f64,
+}
+
+#[derive(Default, Resource)]
+pub(super) struct VoiceLimiter {
+    pending: HashMap<&'static str, Pending>,
+    last_start: HashMap<&'static str, f64>,
+}
+
+pub(super) fn play_sounds(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    time: Res<Time<Real>>,
+    sounds: Res<Sounds>,
+    banks: Res<Assets<SoundBank>>,
+    mut reader: EventReader<PlaySound>,
+    mut limiter: ResMut<VoiceLimiter>,
+    voices: Query<&Voice>,
+) {
+    let now = time.elapsed_secs_f64();
+
+    for sound in reader.read() {
+        limiter
+            .pending
+            .entry(sound.cue)
+            .and_modify(|pending| pending.count += sound.count)
+            .or_insert(Pending {
+                count: sound.count,
+                since: now,
+            });
+    }
+
+    // Requests wait for the bank to load, within the usual latency.
+    let Some(bank) = banks.get(&sounds.0) else {
+        limiter
+            .pending
+            .retain(|_, pending| now - pending.since < MAX_LATENCY);
+        return;
+    };
+
+    let VoiceLimiter {
+        pending,
+        last_start,
+    } = &mut *limiter;
+
+    pending.retain(|name, pending| {
+        let Some(cue) = bank.get(name) else {
+            warn!("no sound cue named `{name}`");
+            return false;
+        };
+
+        let playing = voices.iter().filter(|voice| voice.0 == *name).count();
+        let ready = last_start
+            .get(name)
+            .is_none_or(|last| now - last >= cue.limit.interval);
+
+        if !ready || playing >= cue.limit.voices {
+            return now - pending.since < MAX_LATENCY;
+        }
+
+        let Some(sample) = cue.sample() else {
+            return false;
+        };
+
+        last_start.insert(name, now);
+        let volume = Volume::Decibels(cue.volume + fold_gain(pending.count));
+        let player = (
+            Voice(name),
+            SamplePlayer::new(server.load(sample.to_owned())).with_volume(volume),
+        );
+
+        let mut entity = match cue.bus {
+            Bus::Ui => commands.spawn((UiPool, player)),
+            Bus::Sfx => commands.spawn((SfxPool, player)),
+            Bus::Music => commands.spawn((MusicCuePool, player)),
+        };
+        if let Some(pitch) = cue.pitch() {
+            entity.insert(pitch);
+        }
+
+        false
+    });
+}
+
+/// Each doubling of folded requests adds 3dB, like that many voices playing at once would.
+fn fold_gain(count: u32) -> f32 {
+    (3. * (count.max(1) as f32).log2()).min(MAX_FOLD_GAIN)
+}
@@ -1,26 +1,24 @@
 use avian2d::prelude::*;
 use bevy::color::palettes::css::YELLOW;
 use bevy::prelude::*;
+use bevy_enhanced_input::events::Fired;
 use bevy_optix::debug::DebugCircle;
 use bevy_tween::{BevyTweenRegisterSystems, component_tween_system};
 
 use crate::Layer;
 use crate::feature::ValidZone;
+use crate::input::DepleteBall;
 use crate::paddle::PaddleBonk;
 use crate::particles::{Emitters, ParticleBundle, ParticleEmitter, transform};
-use crate::state::{Playing, StateAppExt, remove_entities};
+use crate::state::{GameState, Playing, StateAppExt, remove_entities};
 
 pub struct BallPlugin;
 
 impl Plugin for BallPlugin {
     fn build(&self, app: &mut App) {
         app.add_reset(remove_entities::<With<BallComponents>>)
-            .add_systems(
-                Update,
-                (despawn_ball, player_ball, recharge)
-                    .chain()
-                    .in_set(Playing),
-            )
+            .add_systems(Update, (despawn_ball, recharge).chain().in_set(Playing))
+            .add_observer(deplete_ball)
             .add_tween_systems(component_tween_system::<PaddleRestMultTween>());
     }
 }
@@ -74,26 +72,29 @@ fn despawn_ball(mut commands: Commands, balls: Query<(Entity, &Transform)>) {
     }
 }
 
-fn player_ball(
+fn deplete_ball(
+    _: Trigger<Fired<DepleteBall>>,
     mut commands: Commands,
-    input: Res<ButtonInput<KeyCode>>,
+    state: Res<State<GameState>>,
     feature_ball: Single<
         (Entity, &Transform),
         (With<PlayerBall>, With<ValidZone>, Without<Depleted>),
     >,
 ) {
-    if input.just_pressed(KeyCode::KeyD) {
-        commands
-            .entity(feature_ball.0)
-            .remove::<(
-                ParticleBundle,
-                DebugCircle,
-                Mesh2d,
-                MeshMaterial2d<ColorMaterial>,
-            )>()
-            .despawn_related::<Emitters>()
-            .insert((Ball, Depleted));
+    if *state.get() != GameState::Playing {
+        return;
     }
+
+    commands
+        .entity(feature_ball.0)
+        .remove::<(
+            ParticleBundle,
+            DebugCircle,
+            Mesh2d,
+            MeshMaterial2d<ColorMaterial>,
+        )>()
+        .despawn_related::<Emitters>()
+        .insert((Ball, Depleted));
 }
 
 fn recharge(
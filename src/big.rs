@@ -9,6 +9,17 @@ impl BigPoints {
     pub fn new(score: i32) -> Self {
         Self(IBig::from(score))
     }
+
+    /// `self / target`, worked out in log space so huge scores don't overflow.
+    pub fn share(&self, target: &BigPoints) -> f32 {
+        if self.0 <= IBig::ZERO || target.0 <= IBig::ZERO {
+            return 0.;
+        }
+
+        let points = self.0.log2_est();
+        let target = target.0.log2_est();
+        (points - target).min(8.).exp2()
+    }
 }
 
 const EXP_THRESHOLD: IBig = dashu::ibig!(1_000_000_000);
@@ -4,22 +4,26 @@ use avian2d::prelude::*;
 
 use bevy::core_pipeline::bloom::Bloom;
 use bevy::prelude::*;
-use bevy::render::render_resource::{AsBindGroup, ShaderRef};
+use bevy::render::render_resource::{AsBindGroup, ShaderRef, ShaderType};
 use bevy::sprite::{AlphaMode2d, Anchor, Material2d, Material2dPlugin};
 use bevy_light_2d::light::{AmbientLight2d, PointLight2d};
 use bevy_optix::camera::MainCamera;
 use bevy_optix::pixel_perfect::{HIGH_RES_LAYER, OuterCamera};
 use bevy_optix::post_process::PostProcessCommand;
-use bevy_tween::combinator::{sequence, tween};
-use bevy_tween::interpolate::translation;
-use bevy_tween::prelude::{AnimationBuilderExt, EaseKind, Interpolator, Repeat};
-use bevy_tween::tween::IntoTarget;
+use bevy_persistent::prelude::*;
+use bevy_tween::prelude::Interpolator;
 use bevy_tween::{BevyTweenRegisterSystems, component_tween_system};
-use std::time::Duration;
+use dashu::integer::IBig;
 
+use crate::accessibility::ScaledText;
 use crate::ball::{Ball, PlayerBall};
-use crate::collectables::{HexColor, Money, Points};
+use crate::big::BigPoints;
+use crate::collectables::{HexColor, Money, PointEvent, Points};
 use crate::float_tween_wrapper;
+use crate::lights::CabinetLight;
+use crate::music::{BeatPulse, CurrentTrack, MusicBeats};
+use crate::settings::Settings;
+use crate::stage::Stage;
 use crate::state::{GameState, StateAppExt, remove_entities};
 
 pub const WIDTH: f32 = 550.;
@@ -31,23 +35,27 @@ pub struct CabinetPlugin;
 
 impl Plugin for CabinetPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<TransitionFinished>()
-            .add_reset(remove_entities::<With<Cabinet>>)
+        app.add_reset(remove_entities::<With<Cabinet>>)
             .add_plugins((
                 Material2dPlugin::<ScrollingTexture>::default(),
                 Material2dPlugin::<Diamonds>::default(),
             ))
             .insert_resource(ClearColor(HexColor(0x090808).into()))
+            .init_resource::<BackgroundState>()
             .add_systems(OnEnter(GameState::StartGame), (spawn, background))
             .add_systems(Startup, lighting)
             .add_systems(
                 Update,
                 (
                     generate_trimesh_colliders,
-                    update_scrolling_background,
+                    (
+                        update_background_state,
+                        update_scrolling_background,
+                        feed_backgrounds,
+                    )
+                        .chain(),
                     points_ui,
                     money_ui,
-                    transition_timer,
                 ),
             )
             .add_tween_systems((
@@ -223,13 +231,29 @@ pub const UIZ: f32 = 100.;
 //     }
 // }
 
-#[derive(Component)]
-struct PointsUI;
+/// The points as last shown, which count up towards the real points.
+#[derive(Default, Component)]
+struct PointsUI(BigPoints);
 
-fn points_ui(mut text: Single<&mut Text2d, With<PointsUI>>, points: Res<Points>) {
-    if points.is_changed() {
-        text.0 = format!("{}", points.get());
+/// Share of the points left to count that is counted each second.
+const POINTS_COUNT_RATE: f32 = 8.;
+
+fn points_ui(time: Res<Time>, text: Single<(&mut Text2d, &mut PointsUI)>, points: Res<Points>) {
+    let (mut text, mut shown) = text.into_inner();
+    let target = &points.get().0;
+    if shown.0.0 == *target && !text.0.is_empty() {
+        return;
     }
+
+    let left = target - &shown.0.0;
+    if left <= IBig::ZERO {
+        // Points only go down when they are reset, which shouldn't count.
+        shown.0 = points.get().clone();
+    } else {
+        let permille = ((POINTS_COUNT_RATE * time.delta_secs()).min(1.) * 1000.) as u32;
+        shown.0.0 += (left * IBig::from(permille) / IBig::from(1000)).max(IBig::ONE);
+    }
+    text.0 = format!("{}", shown.0);
 }
 
 #[derive(Component)]
@@ -304,13 +328,14 @@ fn spawn(mut commands: Commands, server: Res<AssetServer>) {
         )),
         Sprite::from_image(server.load("textures/panel.png")),
         children![(
-            PointsUI,
+            PointsUI::default(),
             Text2d::default(),
             TextFont {
                 font: server.load("fonts/cube.ttf"),
                 font_size: 25.,
                 ..Default::default()
             },
+            ScaledText(25.),
             Anchor::TopLeft,
             t,
         )],
@@ -332,6 +357,7 @@ fn spawn(mut commands: Commands, server: Res<AssetServer>) {
                 font_size: 25.,
                 ..Default::default()
             },
+            ScaledText(25.),
             Anchor::TopLeft,
             t,
         )],
@@ -479,14 +505,16 @@ fn spawn(mut commands: Commands, server: Res<AssetServer>) {
 }
 
 fn spawn_light(commands: &mut Commands, color: impl Into<Color>) {
+    let color = color.into();
     let entity = commands
         .spawn((
             Cabinet,
+            CabinetLight { color },
             PointLight2d {
                 intensity: 2.0,
                 radius: 1024.,
                 cast_shadows: true,
-                color: color.into(),
+                color,
                 falloff: 0.,
                 ..Default::default()
             },
@@ -513,22 +541,16 @@ fn spawn_light(commands: &mut Commands, color: impl Into<Color>) {
         ))
         .id();
 
-    commands
-        .entity(entity)
-        .animation()
-        .repeat(Repeat::Infinitely)
-        .repeat_style(bevy_tween::prelude::RepeatStyle::PingPong)
-        .insert_tween_here(
-            Duration::from_secs_f32(1.),
-            EaseKind::SineInOut,
-            entity.into_target().with(
-                //    rotation(
-                //    Quat::from_rotation_z(-PI / 8. + PI),
-                //    Quat::from_rotation_z(PI / 8. + PI),
-                //)
-                point_light_intensity(5., 4.8),
-            ),
-        );
+    // The pulse is a light of its own so it doesn't fight the lighting.
+    commands.entity(entity).with_child((
+        BeatPulse::new(2.),
+        PointLight2d {
+            radius: 1024.,
+            color,
+            falloff: 0.,
+            ..Default::default()
+        },
+    ));
 }
 
 #[derive(Component)]
@@ -538,14 +560,89 @@ fn background(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut diamonds: ResMut<Assets<Diamonds>>,
+    state: Res<BackgroundState>,
 ) {
     commands.spawn((
         Cabinet,
         Mesh2d(meshes.add(Rectangle::new(1125., 1125.))),
-        MeshMaterial2d(diamonds.add(Diamonds {})),
+        MeshMaterial2d(diamonds.add(Diamonds { background: *state })),
     ));
 }
 
+/// What the background shaders know about play, fed to every background material each
+/// frame.
+#[derive(Debug, Clone, Copy, Resource, ShaderType)]
+pub struct BackgroundState {
+    /// Seconds of virtual time, so the backdrop stops with the game.
+    pub time: f32,
+    /// How far the music is through its current beat, from 0 to 1.
+    pub beat: f32,
+    /// How strongly the backdrop pulses on the beat, 0 with reduced flashing.
+    pub flash: f32,
+    /// How hard points are coming in, from 0 to 1.
+    pub intensity: f32,
+    pub light: LinearRgba,
+    pub dark: LinearRgba,
+    /// What the backdrop is tinted towards as the intensity rises.
+    pub accent: LinearRgba,
+}
+
+impl Default for BackgroundState {
+    fn default() -> Self {
+        Self {
+            time: 0.,
+            beat: 0.,
+            flash: 1.,
+            intensity: 0.,
+            light: LinearRgba::rgb(0.2, 0.22352, 0.2549019),
+            dark: LinearRgba::rgb(0.05, 0.05, 0.05),
+            accent: LinearRgba::rgb(0.56, 0.04, 0.34),
+        }
+    }
+}
+
+/// Points worth this share of the stage target, scored at once, max out the intensity.
+const INTENSITY_SHARE: f32 = 0.25;
+/// How much of the intensity fades each second.
+const INTENSITY_DECAY: f32 = 0.5;
+
+fn update_background_state(
+    time: Res<Time>,
+    settings: Res<Persistent<Settings>>,
+    mut state: ResMut<BackgroundState>,
+    mut points: EventReader<PointEvent>,
+    stage: Option<Single<&Stage>>,
+    beats: Option<Single<&MusicBeats, With<CurrentTrack>>>,
+) {
+    state.time = time.elapsed_secs_wrapped();
+    state.beat = beats
+        .filter(|beats| beats.get() >= 0.)
+        .map_or(0., |beats| beats.get().fract());
+    state.flash = if settings.reduced_flashing { 0. } else { 1. };
+
+    let gained = BigPoints(
+        points
+            .read()
+            .fold(IBig::ZERO, |gained, event| gained + &event.points.0),
+    );
+    let share = stage.map_or(0., |stage| gained.share(&stage.points));
+    state.intensity = (state.intensity + share / INTENSITY_SHARE).min(1.);
+    state.intensity = (state.intensity - INTENSITY_DECAY * time.delta_secs()).max(0.);
+}
+
+fn feed_backgrounds(
+    state: Res<BackgroundState>,
+    mut scrolling: ResMut<Assets<ScrollingTexture>>,
+    mut diamonds: ResMut<Assets<Diamonds>>,
+) {
+    for (_, material) in scrolling.iter_mut() {
+        material.background = *state;
+    }
+    for (_, material) in diamonds.iter_mut() {
+        material.background = *state;
+    }
+}
+
 fn update_scrolling_background(
     query: Query<(&MeshMaterial2d<ScrollingTexture>, &Speed)>,
     mut materials: ResMut<Assets<ScrollingTexture>>,
@@ -570,6 +667,8 @@ pub struct ScrollingTexture {
     pub texture: Handle<Image>,
     #[uniform(2)]
     pub uv_offset: Vec2,
+    #[uniform(3)]
+    pub background: BackgroundState,
 }
 
 impl Material2d for ScrollingTexture {
@@ -583,88 +682,13 @@ impl Material2d for ScrollingTexture {
 }
 
 #[derive(Clone, Asset, TypePath, AsBindGroup)]
-struct Diamonds {}
+struct Diamonds {
+    #[uniform(0)]
+    background: BackgroundState,
+}
 
 impl Material2d for Diamonds {
     fn fragment_shader() -> ShaderRef {
         "shaders/diamonds.wgsl".into()
     }
 }
-
-#[derive(Event)]
-pub struct TransitionFinished;
-
-#[derive(Component)]
-struct TransitionTimer(Timer);
-
-fn transition_timer(
-    mut commands: Commands,
-    time: Res<Time>,
-    timer: Single<(Entity, &mut TransitionTimer)>,
-    mut writer: EventWriter<TransitionFinished>,
-) {
-    let (entity, mut timer) = timer.into_inner();
-
-    timer.0.tick(time.delta());
-    if timer.0.finished() {
-        writer.write(TransitionFinished);
-        commands.entity(entity).despawn();
-    }
-}
-
-pub fn transition(mut commands: Commands, server: Res<AssetServer>) {
-    let dur = 1.;
-    let mut stagger = false;
-    for y in 0..1024 / 62 {
-        let right = Vec3::new(
-            crate::WIDTH / 2. + 1024.,
-            y as f32 * 62. - crate::HEIGHT / 2.,
-            980.,
-        );
-        let left = Vec3::new(
-            -crate::WIDTH / 2. - 1024.,
-            y as f32 * 62. - crate::HEIGHT / 2.,
-            980.,
-        );
-        let middle = Vec3::new(0., y as f32 * 62. - crate::HEIGHT / 2., 980.);
-
-        let (start, end) = if stagger {
-            (right, left)
-        } else {
-            (left, right)
-        };
-
-        let slider = commands
-            .spawn((
-                Cabinet,
-                HIGH_RES_LAYER,
-                Transform::from_scale(Vec3::splat(crate::RESOLUTION_SCALE).with_z(998.)),
-                Sprite::from_image(server.load("textures/slider.png")),
-            ))
-            .id();
-
-        let animation = commands
-            .animation()
-            .insert(sequence((
-                tween(
-                    Duration::from_secs_f32(dur / 2.),
-                    EaseKind::ExponentialOut,
-                    slider.into_target().with(translation(start, middle)),
-                ),
-                tween(
-                    Duration::from_secs_f32(dur / 2.),
-                    EaseKind::ExponentialIn,
-                    slider.into_target().with(translation(middle, end)),
-                ),
-            )))
-            .id();
-        commands.entity(slider).add_child(animation);
-
-        stagger = !stagger;
-    }
-
-    commands.spawn(TransitionTimer(Timer::from_seconds(
-        dur / 2.,
-        TimerMode::Once,
-    )));
-}
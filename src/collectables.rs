@@ -1,12 +1,8 @@
-use crate::RESOLUTION_SCALE;
+use crate::audio::PlaySound;
 use crate::big::BigPoints;
 use crate::state::{StateAppExt, insert_resource};
-use crate::text::flash_text_rotate;
 use bevy::prelude::*;
-use bevy_seedling::prelude::*;
 use dashu::integer::IBig;
-use rand::Rng;
-use std::f32::consts::PI;
 
 pub const POINT_COLOR: HexColor = HexColor(0xfff540);
 pub const MONEY_COLOR: HexColor = HexColor(0x00ff00);
@@ -74,6 +70,10 @@ impl Into<Color> for HexColor {
 pub struct TotalPoints(BigPoints);
 
 impl TotalPoints {
+    pub fn new(points: BigPoints) -> Self {
+        Self(points)
+    }
+
     pub fn get(&self) -> &BigPoints {
         &self.0
     }
@@ -83,6 +83,10 @@ impl TotalPoints {
 pub struct Points(BigPoints);
 
 impl Points {
+    pub fn new(points: BigPoints) -> Self {
+        Self(points)
+    }
+
     pub fn get(&self) -> &BigPoints {
         &self.0
     }
@@ -102,6 +106,10 @@ pub struct PointEvent {
 pub struct Money(i32);
 
 impl Money {
+    pub fn new(money: i32) -> Self {
+        Self(money)
+    }
+
     pub fn get(&self) -> i32 {
         self.0
     }
@@ -113,55 +121,26 @@ pub struct MoneyEvent {
     pub position: Vec2,
 }
 
+/// Tallies points and money. Their labels are shown by [`popup`](crate::popup).
 fn effects(
-    mut commands: Commands,
-    server: Res<AssetServer>,
+    mut sounds: EventWriter<PlaySound>,
     mut points: EventReader<PointEvent>,
     mut money: EventReader<MoneyEvent>,
     mut total_points: ResMut<Points>,
     mut total_total_points: ResMut<TotalPoints>,
     mut total_money: ResMut<Money>,
 ) {
-    if !points.is_empty() || !money.is_empty() {
-        commands.spawn(
-            SamplePlayer::new(server.load("audio/score.ogg")).with_volume(Volume::Linear(0.5)),
-        );
+    let events = points.len() + money.len();
+    if events > 0 {
+        sounds.write(PlaySound::new("points.score").times(events as u32));
     }
 
-    let mut rng = rand::thread_rng();
-    let rot = PI / 9.;
-
     for event in points.read() {
         total_points.0.0 += event.points.0.clone();
         total_total_points.0.0 += event.points.0.clone();
-        flash_text_rotate(
-            &mut commands,
-            &server,
-            format!("+{}", event.points),
-            SIZE,
-            (event.position * RESOLUTION_SCALE).extend(POINT_TEXT_Z),
-            rng.gen_range(-rot..rot),
-            POINT_COLOR,
-        );
     }
 
     for event in money.read() {
         total_money.0 += event.money;
-
-        let money_color = if event.money >= 0 {
-            MONEY_COLOR
-        } else {
-            MONEY_COLOR_REMOVE
-        };
-
-        flash_text_rotate(
-            &mut commands,
-            &server,
-            format!("${}", event.money),
-            SIZE,
-            (event.position * RESOLUTION_SCALE).extend(POINT_TEXT_Z),
-            rng.gen_range(-rot..rot),
-            money_color,
-        );
     }
 }
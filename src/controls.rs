@@ -0,0 +1,266 @@
+use bevy::prelude::*;
+use bevy_enhanced_input::events::Fired;
+use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
+use bevy_persistent::prelude::*;
+
+use crate::cabinet::ScrollingTexture;
+use crate::collectables::POINT_COLOR;
+use crate::input::{Back, Control, Controls, Enter, FocusDown, FocusUp, InputButton};
+use crate::leaderboard::checkers;
+use crate::state::{self, GameState, remove_entities};
+use crate::touch::Taps;
+use crate::transition::{GoTo, MENU_FADE_SECS, Transition};
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ControlsFocus::default())
+            .add_systems(OnEnter(GameState::Controls), spawn_controls)
+            .add_systems(
+                OnExit(GameState::Controls),
+                remove_entities::<With<ControlsScreen>>,
+            )
+            .add_systems(
+                Update,
+                (capture, tap_to_menu, update_rows)
+                    .chain()
+                    .in_set(state::Controls),
+            )
+            .add_observer(focus_up)
+            .add_observer(focus_down)
+            .add_observer(select)
+            .add_observer(back_to_menu);
+    }
+}
+
+/// The row after the last [`Control`] restores every default.
+const RESET_ROW: usize = Control::ALL.len();
+
+#[derive(Default, Resource)]
+struct ControlsFocus {
+    row: usize,
+    capture: Option<Capture>,
+}
+
+struct Capture {
+    control: Control,
+    /// Skips the frame the capture started on so the confirming press isn't captured.
+    armed: bool,
+}
+
+impl ControlsFocus {
+    fn is_idle(&self, state: &State<GameState>) -> bool {
+        *state.get() == GameState::Controls && self.capture.is_none()
+    }
+}
+
+#[derive(Component)]
+struct ControlsScreen;
+
+#[derive(Component)]
+struct ControlRow(usize);
+
+const SCREENZ: f32 = 800.;
+
+fn spawn_controls(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    mut focus: ResMut<ControlsFocus>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mats: ResMut<Assets<ScrollingTexture>>,
+) {
+    *focus = ControlsFocus::default();
+
+    commands.spawn((ControlsScreen, checkers(&server, &mut meshes, &mut mats)));
+
+    commands.spawn((
+        ControlsScreen,
+        Text2d::new("CONTROLS"),
+        TextFont {
+            font_size: 54.,
+            font: server.load("fonts/saiba.ttf"),
+            ..Default::default()
+        },
+        HIGH_RES_LAYER,
+        Transform::from_xyz(0., crate::RES_HEIGHT / 3., SCREENZ),
+    ));
+
+    for row in 0..=RESET_ROW {
+        commands.spawn((
+            ControlsScreen,
+            ControlRow(row),
+            Text2d::default(),
+            TextFont {
+                font_size: 24.,
+                ..Default::default()
+            },
+            HIGH_RES_LAYER,
+            Transform::from_xyz(
+                0.,
+                crate::RES_HEIGHT / 3. - 80. - (row as f32 * 36.),
+                SCREENZ,
+            ),
+        ));
+    }
+}
+
+fn update_rows(
+    focus: Res<ControlsFocus>,
+    controls: Res<Persistent<Controls>>,
+    mut rows: Query<(Ref<ControlRow>, &mut Text2d, &mut TextColor)>,
+) {
+    for (row, mut text, mut color) in rows.iter_mut() {
+        if !focus.is_changed() && !controls.is_changed() && !row.is_added() {
+            continue;
+        }
+
+        text.0 = match Control::ALL.get(row.0) {
+            Some(control) => {
+                let buttons = match &focus.capture {
+                    Some(capture) if capture.control == *control => {
+                        String::from("PRESS A BUTTON...")
+                    }
+                    _ => controls
+                        .get(*control)
+                        .iter()
+                        .map(InputButton::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" / "),
+                };
+                format!("{}   {buttons}", control.label())
+            }
+            None => String::from("RESET DEFAULTS"),
+        };
+        color.0 = if row.0 == focus.row {
+            POINT_COLOR.into()
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+fn focus_up(
+    _: Trigger<Fired<FocusUp>>,
+    state: Res<State<GameState>>,
+    mut focus: ResMut<ControlsFocus>,
+) {
+    if focus.is_idle(&state) {
+        focus.row = focus.row.checked_sub(1).unwrap_or(RESET_ROW);
+    }
+}
+
+fn focus_down(
+    _: Trigger<Fired<FocusDown>>,
+    state: Res<State<GameState>>,
+    mut focus: ResMut<ControlsFocus>,
+) {
+    if focus.is_idle(&state) {
+        focus.row = (focus.row + 1) % (RESET_ROW + 1);
+    }
+}
+
+fn select(
+    _: Trigger<Fired<Enter>>,
+    state: Res<State<GameState>>,
+    mut focus: ResMut<ControlsFocus>,
+    mut controls: ResMut<Persistent<Controls>>,
+) {
+    if !focus.is_idle(&state) {
+        return;
+    }
+
+    match Control::ALL.get(focus.row) {
+        Some(control) => {
+            focus.capture = Some(Capture {
+                control: *control,
+                armed: false,
+            });
+        }
+        None => {
+            controls.reset();
+            if let Err(e) = controls.persist() {
+                error!("failed to save controls: {e}");
+            }
+        }
+    }
+}
+
+fn back_to_menu(
+    _: Trigger<Fired<Back>>,
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    focus: Res<ControlsFocus>,
+) {
+    if focus.is_idle(&state) {
+        commands.send_event(GoTo::new(
+            GameState::Menu,
+            Transition::fade().with_duration(MENU_FADE_SECS),
+        ));
+    }
+}
+
+/// Rebinding needs a keyboard or gamepad, so any tap leaves the screen.
+fn tap_to_menu(mut commands: Commands, mut taps: Taps, focus: Res<ControlsFocus>) {
+    if taps.read().count() > 0 && focus.capture.is_none() {
+        commands.send_event(GoTo::new(
+            GameState::Menu,
+            Transition::fade().with_duration(MENU_FADE_SECS),
+        ));
+    }
+}
+
+/// Binds the next button pressed to the captured [`Control`], replacing the
+/// previous bindings from the same kind of device. Escape cancels.
+fn capture(
+    mut focus: ResMut<ControlsFocus>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut controls: ResMut<Persistent<Controls>>,
+) {
+    let Some(capture) = focus.capture.as_mut() else {
+        return;
+    };
+
+    if !capture.armed {
+        capture.armed = true;
+        return;
+    }
+    let control = capture.control;
+
+    if keys.just_pressed(KeyCode::Escape) {
+        focus.capture = None;
+        return;
+    }
+
+    let Some(button) = keys
+        .get_just_pressed()
+        .next()
+        .map(|key| InputButton::Key(*key))
+        .or_else(|| {
+            mouse
+                .get_just_pressed()
+                .next()
+                .map(|button| InputButton::Mouse(*button))
+        })
+        .or_else(|| {
+            gamepads
+                .iter()
+                .find_map(|gamepad| gamepad.get_just_pressed().next())
+                .map(|button| InputButton::Gamepad(*button))
+        })
+    else {
+        return;
+    };
+
+    let mut buttons = controls.get(control);
+    buttons.retain(|other| other.is_gamepad() != button.is_gamepad());
+    buttons.push(button);
+    controls.set(control, buttons);
+    if let Err(e) = controls.persist() {
+        error!("failed to save controls: {e}");
+    }
+
+    focus.capture = None;
+}
@@ -0,0 +1,362 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_persistent::prelude::*;
+use bevy_tween::interpolate::scale;
+use bevy_tween::prelude::{AnimationBuilderExt, EaseKind};
+use bevy_tween::tween::IntoTarget;
+
+use super::Bonks;
+use crate::accessibility::Palette;
+use crate::animation::{AnimationAppExt, AnimationController, AnimationIndices, AnimationSprite};
+use crate::ball::BallComponents;
+use crate::settings::Settings;
+use crate::sprites::{CellSize, sprite_rect};
+
+pub struct FeatureArtPlugin;
+
+impl Plugin for FeatureArtPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_layout(
+            ATLAS,
+            TextureAtlasLayout::from_grid(UVec2::splat(CELL), COLUMNS, ROWS, None, None),
+        )
+        .add_systems(
+            Update,
+            (deplete_art, settle_art, animate_art, apply_palette).chain(),
+        )
+        .add_observer(spawn_art)
+        .add_observer(hit_art);
+    }
+}
+
+/// Every feature's frames, one row per feature.
+///
+/// Columns 0 to 3 idle, 4 and 5 are the hit flash, 6 is depleted and 7 is the glow.
+const ATLAS: &str = "textures/features.png";
+/// [`ATLAS`] in grays, tinted for palettes that recolor features.
+const GRAY_ATLAS: &str = "textures/features-gray.png";
+const CELL: u32 = 24;
+const COLUMNS: u32 = 8;
+const ROWS: u32 = 8;
+
+const IDLE_FRAMES: [usize; 4] = [0, 1, 2, 3];
+const HIT_FRAMES: [usize; 2] = [4, 5];
+const DEPLETED_FRAME: usize = 6;
+const GLOW_CELL: u32 = 7;
+
+const IDLE_FRAME_SECS: f32 = 0.15;
+const HIT_FRAME_SECS: f32 = 0.06;
+const HIT_SECS: f32 = 0.12;
+const SQUASH_SECS: f32 = 0.18;
+
+/// Art sits just under the balls and the glow under the art.
+const ART_Z: f32 = -1.;
+const GLOW_Z: f32 = -2.;
+
+const GLOW_ALPHA: f32 = 0.25;
+const GLOW_HIT_ALPHA: f32 = 0.8;
+/// Glow alpha lost per second after a hit.
+const GLOW_FADE: f32 = 3.;
+
+/// Letters drawn over each row's features for palettes that code more than color.
+const ICONS: [&str; ROWS as usize] = ["B", "X", "$", "D", "R", "L", "S", "I"];
+const ICON_Z: f32 = -0.5;
+const ICON_SIZE: f32 = 12.;
+
+/// A limited feature shows as depleted when it has this many bonks left.
+const DEPLETED_BONKS: usize = 1;
+
+/// How a feature is drawn: its row in the feature atlas, its size and the color it glows.
+#[derive(Clone, Copy, Component)]
+#[require(Transform, Visibility)]
+pub struct FeatureArt {
+    row: usize,
+    size: f32,
+    glow: Color,
+    rotation: f32,
+}
+
+impl FeatureArt {
+    pub const fn new(row: usize, size: f32, glow: Srgba) -> Self {
+        Self {
+            row,
+            size,
+            glow: Color::Srgba(glow),
+            rotation: 0.,
+        }
+    }
+
+    /// Turns the art, for features that point somewhere.
+    pub const fn rotated(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    fn frames(&self, frames: impl IntoIterator<Item = usize>) -> Vec<usize> {
+        frames
+            .into_iter()
+            .map(|frame| self.row * COLUMNS as usize + frame)
+            .collect()
+    }
+
+    fn scale(&self) -> Vec3 {
+        Vec3::new(self.size / CELL as f32, self.size / CELL as f32, 1.)
+    }
+
+    fn glow(&self, palette: Palette) -> Color {
+        palette.feature(self.row).unwrap_or(self.glow)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+enum ArtState {
+    Idle,
+    Hit,
+    Depleted,
+}
+
+/// The animated sprite of a feature, kept off the feature itself so squashing it
+/// leaves the collider alone.
+#[derive(Component)]
+struct ArtSprite {
+    feature: Entity,
+    hit: Timer,
+}
+
+#[derive(Component)]
+struct ArtGlow;
+
+#[derive(Component)]
+struct ArtIcon;
+
+fn icon_visibility(palette: Palette) -> Visibility {
+    if palette.icons() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+fn spawn_art(
+    trigger: Trigger<OnAdd, FeatureArt>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    settings: Res<Persistent<Settings>>,
+    art: Query<&FeatureArt>,
+) {
+    let feature = trigger.target();
+    let Ok(art) = art.get(feature) else {
+        return;
+    };
+
+    let mut glow = sprite_rect(&server, ATLAS, CellSize::TwentyFour, glow_cell(art));
+    glow.color = art.glow(settings.palette).with_alpha(GLOW_ALPHA);
+    glow.custom_size = Some(Vec2::splat(art.size * 2.));
+
+    commands
+        .entity(feature)
+        .insert(ArtState::Idle)
+        .with_children(|parent| {
+            parent.spawn((
+                ArtSprite {
+                    feature,
+                    hit: Timer::from_seconds(HIT_SECS, TimerMode::Once),
+                },
+                AnimationSprite::repeating(ATLAS, IDLE_FRAME_SECS, art.frames(IDLE_FRAMES)),
+                Transform::from_xyz(0., 0., ART_Z)
+                    .with_scale(art.scale())
+                    .with_rotation(Quat::from_rotation_z(art.rotation)),
+            ));
+            parent.spawn((ArtGlow, glow, Transform::from_xyz(0., 0., GLOW_Z)));
+            parent.spawn((
+                ArtIcon,
+                Text2d::new(ICONS.get(art.row).copied().unwrap_or_default()),
+                TextFont {
+                    font: server.load("fonts/cube.ttf"),
+                    font_size: ICON_SIZE,
+                    ..Default::default()
+                },
+                icon_visibility(settings.palette),
+                Transform::from_xyz(0., 0., ICON_Z),
+            ));
+        });
+}
+
+fn glow_cell(art: &FeatureArt) -> UVec2 {
+    UVec2::new(GLOW_CELL, art.row as u32)
+}
+
+fn hit_art(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    balls: Query<(), With<BallComponents>>,
+    mut features: Query<(&FeatureArt, &mut ArtState, &Children)>,
+    mut sprites: Query<(
+        Entity,
+        &mut ArtSprite,
+        &mut AnimationController,
+        &mut Sprite,
+    )>,
+    mut glows: Query<&mut Sprite, (With<ArtGlow>, Without<ArtSprite>)>,
+) {
+    if !balls.contains(trigger.collider) {
+        return;
+    }
+    let Ok((art, mut state, children)) = features.get_mut(trigger.target()) else {
+        return;
+    };
+
+    *state = ArtState::Hit;
+    for child in children.iter() {
+        if let Ok((entity, mut sprite, mut controller, mut image)) = sprites.get_mut(child) {
+            sprite.hit.reset();
+            play(
+                &mut controller,
+                &mut image,
+                AnimationIndices::once(art.frames(HIT_FRAMES)),
+                HIT_FRAME_SECS,
+            );
+
+            let squashed = art.scale() * Vec3::new(1.25, 0.8, 1.);
+            commands.entity(entity).animation().insert_tween_here(
+                Duration::from_secs_f32(SQUASH_SECS),
+                EaseKind::BackOut,
+                entity.into_target().with(scale(squashed, art.scale())),
+            );
+        }
+
+        if let Ok(mut glow) = glows.get_mut(child) {
+            glow.color.set_alpha(GLOW_HIT_ALPHA);
+        }
+    }
+}
+
+fn depleted(bonks: Option<&Bonks>) -> bool {
+    matches!(bonks, Some(Bonks::Limited(left)) if *left <= DEPLETED_BONKS)
+}
+
+fn deplete_art(mut features: Query<(&Bonks, &mut ArtState), Changed<Bonks>>) {
+    for (bonks, mut state) in features.iter_mut() {
+        if depleted(Some(bonks)) && *state == ArtState::Idle {
+            *state = ArtState::Depleted;
+        }
+    }
+}
+
+/// Ends hits once they have played out and fades the glow back.
+fn settle_art(
+    time: Res<Time>,
+    mut features: Query<(&mut ArtState, Option<&Bonks>)>,
+    mut sprites: Query<&mut ArtSprite>,
+    mut glows: Query<&mut Sprite, With<ArtGlow>>,
+) {
+    for mut sprite in sprites.iter_mut() {
+        if !sprite.hit.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let Ok((mut state, bonks)) = features.get_mut(sprite.feature) else {
+            continue;
+        };
+        if *state == ArtState::Hit {
+            *state = if depleted(bonks) {
+                ArtState::Depleted
+            } else {
+                ArtState::Idle
+            };
+        }
+    }
+
+    for mut glow in glows.iter_mut() {
+        let alpha = glow.color.alpha();
+        if alpha > GLOW_ALPHA {
+            glow.color
+                .set_alpha((alpha - GLOW_FADE * time.delta_secs()).max(GLOW_ALPHA));
+        }
+    }
+}
+
+/// Loops the idle or depleted frames when a feature settles into either.
+fn animate_art(
+    features: Query<(&FeatureArt, &ArtState, &Children), Changed<ArtState>>,
+    mut sprites: Query<(&mut AnimationController, &mut Sprite), With<ArtSprite>>,
+) {
+    for (art, state, children) in features.iter() {
+        let frames = match state {
+            ArtState::Idle => art.frames(IDLE_FRAMES),
+            ArtState::Depleted => art.frames([DEPLETED_FRAME]),
+            ArtState::Hit => continue,
+        };
+
+        let mut sprites = sprites.iter_many_mut(children);
+        while let Some((mut controller, mut sprite)) = sprites.fetch_next() {
+            play(
+                &mut controller,
+                &mut sprite,
+                AnimationIndices::repeating(frames.clone()),
+                IDLE_FRAME_SECS,
+            );
+        }
+    }
+}
+
+fn play(
+    controller: &mut AnimationController,
+    sprite: &mut Sprite,
+    indices: AnimationIndices,
+    secs: f32,
+) {
+    if let Some(atlas) = sprite.texture_atlas.as_mut() {
+        atlas.index = indices.start();
+    }
+    *controller = AnimationController::from_seconds(indices, secs);
+}
+
+/// Recolors bodies and glows and shows or hides icons when the palette changes.
+///
+/// Bodies get their sprite a frame after the art spawns, so new ones are painted then.
+fn apply_palette(
+    server: Res<AssetServer>,
+    settings: Res<Persistent<Settings>>,
+    features: Query<(&FeatureArt, &Children)>,
+    mut bodies: Query<(&ArtSprite, &mut Sprite), Without<ArtGlow>>,
+    mut glows: Query<&mut Sprite, With<ArtGlow>>,
+    mut icons: Query<&mut Visibility, With<ArtIcon>>,
+) {
+    let changed = settings.is_changed();
+    for (body, mut sprite) in bodies.iter_mut() {
+        if !changed && !sprite.is_added() {
+            continue;
+        }
+        let Ok((art, _)) = features.get(body.feature) else {
+            continue;
+        };
+
+        // The standard art keeps the colors it was drawn with.
+        let (atlas, color) = match settings.palette.feature(art.row) {
+            Some(color) => (GRAY_ATLAS, color),
+            None => (ATLAS, Color::WHITE),
+        };
+        sprite.image = server.load(atlas);
+        sprite.color = color;
+    }
+
+    if !changed {
+        return;
+    }
+
+    for (art, children) in features.iter() {
+        let mut sprites = glows.iter_many_mut(children);
+        while let Some(mut glow) = sprites.fetch_next() {
+            let alpha = glow.color.alpha();
+            glow.color = art.glow(settings.palette).with_alpha(alpha);
+        }
+
+        let mut icons = icons.iter_many_mut(children);
+        while let Some(mut visibility) = icons.fetch_next() {
+            *visibility = icon_visibility(settings.palette);
+        }
+    }
+}
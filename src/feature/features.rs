@@ -5,20 +5,24 @@ use avian2d::prelude::*;
 use bevy::color::palettes::css::{BLUE, GREEN, MAROON, PURPLE, RED, YELLOW};
 use bevy::color::palettes::tailwind::CYAN_700;
 use bevy::prelude::*;
-use bevy_optix::debug::DebugCircle;
-use bevy_seedling::prelude::Volume;
-use bevy_seedling::sample::SamplePlayer;
+use bevy::reflect::Typed;
 use dashu::ibig;
 
+use crate::audio::PlaySound;
 use crate::ball::{Ball, BallComponents, PlayerBall};
 use crate::big::BigPoints;
 use crate::collectables::{MoneyEvent, PointEvent};
+use crate::hitstop::HitStop;
 use crate::paddle::PaddleBonk;
+use crate::particles::Burst;
+use crate::rng::RunRng;
 use crate::sampler::Sampler;
+use crate::save::{PlayerData, PlayerSave};
 use crate::state::{GameState, Playing};
 use crate::tooltips::Tooltips;
 
-use super::{BonkImpulse, Bonks, FeatureCooldown, Points, feature_cooldown};
+use super::art::FeatureArt;
+use super::{BonkBurst, BonkImpulse, Bonks, FeatureCooldown, Points, feature_cooldown};
 
 pub const MAX_BALLS: usize = 2000;
 pub const FEATURE_SIZE: f32 = 36.0;
@@ -85,18 +89,30 @@ impl FeatureSpawner {
     }
 }
 
-pub fn spawn_feature_list(mut commands: Commands) {
-    commands.spawn((Bumper, Rarity::Common, feature_bundle()));
-    commands.spawn((Dispenser, Rarity::Common, feature_bundle()));
-    commands.spawn((MoneyBumper, Rarity::Uncommon, feature_bundle()));
-    commands.spawn((BingBong, Rarity::Uncommon, feature_bundle()));
-    commands.spawn((Splitter::default(), Rarity::Uncommon, feature_bundle()));
-    commands.spawn((Lotto, Rarity::Uncommon, feature_bundle()));
-    commands.spawn((NorthWestRedirector, Rarity::Rare, feature_bundle()));
-    commands.spawn((NorthEastRedirector, Rarity::Rare, feature_bundle()));
-    commands.spawn((SouthWestRedirector, Rarity::Rare, feature_bundle()));
-    commands.spawn((SouthEastRedirector, Rarity::Rare, feature_bundle()));
-    commands.spawn((FieldInverter, Rarity::Rare, feature_bundle()));
+pub fn spawn_feature_list(mut commands: Commands, data: Res<PlayerSave>) {
+    register(&mut commands, &data, Bumper, Rarity::Common);
+    register(&mut commands, &data, Dispenser, Rarity::Common);
+    register(&mut commands, &data, MoneyBumper, Rarity::Uncommon);
+    register(&mut commands, &data, BingBong, Rarity::Uncommon);
+    register(&mut commands, &data, Splitter::default(), Rarity::Uncommon);
+    register(&mut commands, &data, Lotto, Rarity::Uncommon);
+    register(&mut commands, &data, NorthWestRedirector, Rarity::Rare);
+    register(&mut commands, &data, NorthEastRedirector, Rarity::Rare);
+    register(&mut commands, &data, SouthWestRedirector, Rarity::Rare);
+    register(&mut commands, &data, SouthEastRedirector, Rarity::Rare);
+    register(&mut commands, &data, FieldInverter, Rarity::Rare);
+}
+
+/// Adds `feature` to the selection pool if the player has unlocked it.
+fn register<T: Component + Typed>(
+    commands: &mut Commands,
+    data: &PlayerData,
+    feature: T,
+    rarity: Rarity,
+) {
+    if data.is_unlocked::<T>() {
+        commands.spawn((feature, rarity, feature_bundle()));
+    }
 }
 
 fn feature_bundle() -> impl Bundle {
@@ -115,25 +131,22 @@ fn feature_bundle() -> impl Bundle {
     Tooltips::new::<Self>(),
     Points(20),
     BonkImpulse(2.),
-    DebugCircle::color(FEATURE_RADIUS, RED),
+    FeatureArt::new(0, FEATURE_SIZE, RED),
+    BonkBurst("particles/bonk-bumper.ron"),
     Collider::circle(FEATURE_RADIUS),
 )]
 pub struct Bumper;
 
 pub fn bumper(
     trigger: Trigger<OnCollisionStart>,
-    mut commands: Commands,
-    server: Res<AssetServer>,
+    mut sounds: EventWriter<PlaySound>,
     bumpers: Query<&Bumper>,
 ) {
     if bumpers.get(trigger.target()).is_err() {
         return;
     }
 
-    commands.spawn(
-        SamplePlayer::new(server.load("audio/pinball/1MetalKLANK.ogg"))
-            .with_volume(Volume::Linear(0.4)),
-    );
+    sounds.write(PlaySound::new("feature.bumper.hit"));
 }
 
 /// Indicates how many times a ball has hit a BingBong.
@@ -154,7 +167,8 @@ fn clear_bing_bong(mut commands: Commands, mut paddle_hit: EventReader<PaddleBon
     Tooltips::new::<Self>(),
     Points(0),
     BonkImpulse(2.),
-    DebugCircle::color(FEATURE_RADIUS, CYAN_700),
+    FeatureArt::new(1, FEATURE_SIZE, CYAN_700),
+    BonkBurst("particles/bonk-bing-bong.ron"),
     Collider::circle(FEATURE_RADIUS),
     Price(2),
 )]
@@ -199,7 +213,8 @@ pub fn bing_bong(
     Points(0),
     Bonks::Limited(3),
     BonkImpulse(1.25),
-    DebugCircle::color(FEATURE_RADIUS * 0.666, YELLOW),
+    FeatureArt::new(2, FEATURE_SIZE * 0.666, YELLOW),
+    BonkBurst("particles/bonk-money-bumper.ron"),
     Collider::circle(FEATURE_RADIUS * 0.666)
 )]
 pub struct MoneyBumper;
@@ -228,7 +243,8 @@ pub fn kaching(
     Tooltips::new::<Self>(),
     Points(10),
     Bonks::Limited(10),
-    DebugCircle::color(FEATURE_RADIUS, GREEN),
+    FeatureArt::new(3, FEATURE_SIZE, GREEN),
+    BonkBurst("particles/bonk-dispenser.ron"),
     Collider::circle(FEATURE_RADIUS)
 )]
 pub struct Dispenser;
@@ -239,6 +255,7 @@ pub fn dispense(
     filtered: Query<&FeatureCooldown<Dispenser>>,
     transforms: Query<&GlobalTransform, With<Dispenser>>,
     mut commands: Commands,
+    mut bursts: EventWriter<Burst>,
 ) {
     if filtered.contains(trigger.collider) {
         return;
@@ -261,6 +278,10 @@ pub fn dispense(
                 feature,
                 LinearVelocity(initial_velocity * 0.75),
             ));
+            bursts.write(Burst::new(
+                "particles/dispense.ron",
+                feature.translation.xy(),
+            ));
         }
     }
 }
@@ -323,7 +344,8 @@ pub fn redirect(
     Feature,
     FeatureSpawner::new::<Self>(),
     Tooltips::new::<Self>(),
-    DebugCircle::color(FEATURE_RADIUS, GREEN),
+    FeatureArt::new(4, FEATURE_SIZE, GREEN).rotated(PI * 0.75),
+    BonkBurst("particles/bonk-redirector.ron"),
 )]
 pub struct NorthWestRedirector;
 
@@ -334,7 +356,8 @@ pub struct NorthWestRedirector;
     Feature,
     FeatureSpawner::new::<Self>(),
     Tooltips::new::<Self>(),
-    DebugCircle::color(FEATURE_RADIUS, GREEN),
+    FeatureArt::new(4, FEATURE_SIZE, GREEN).rotated(PI * 0.25),
+    BonkBurst("particles/bonk-redirector.ron"),
 )]
 pub struct NorthEastRedirector;
 
@@ -345,7 +368,8 @@ pub struct NorthEastRedirector;
     Feature,
     FeatureSpawner::new::<Self>(),
     Tooltips::new::<Self>(),
-    DebugCircle::color(FEATURE_RADIUS, GREEN),
+    FeatureArt::new(4, FEATURE_SIZE, GREEN).rotated(PI * 1.25),
+    BonkBurst("particles/bonk-redirector.ron"),
 )]
 pub struct SouthWestRedirector;
 
@@ -356,7 +380,8 @@ pub struct SouthWestRedirector;
     Feature,
     FeatureSpawner::new::<Self>(),
     Tooltips::new::<Self>(),
-    DebugCircle::color(FEATURE_RADIUS, GREEN),
+    FeatureArt::new(4, FEATURE_SIZE, GREEN).rotated(PI * 1.75),
+    BonkBurst("particles/bonk-redirector.ron"),
 )]
 pub struct SouthEastRedirector;
 
@@ -367,7 +392,8 @@ pub struct SouthEastRedirector;
     FeatureSpawner::new::<Self>(),
     Tooltips::new::<Self>(),
     BonkImpulse(1.25),
-    DebugCircle::color(FEATURE_RADIUS, PURPLE),
+    FeatureArt::new(5, FEATURE_SIZE, PURPLE),
+    BonkBurst("particles/bonk-lotto.ron"),
     Collider::circle(FEATURE_RADIUS)
 )]
 pub struct Lotto;
@@ -376,16 +402,21 @@ pub fn lotto(
     trigger: Trigger<OnCollisionStart>,
     transforms: Query<&GlobalTransform, With<Lotto>>,
     mut event_writer: EventWriter<MoneyEvent>,
+    mut stops: EventWriter<HitStop>,
+    mut rng: ResMut<RunRng>,
 ) {
     let Ok(transform) = transforms.get(trigger.target()) else {
         return;
     };
 
-    let mut rng = rand::thread_rng();
     let probability = Sampler::new(&[(-1, 4.0), (7, 1.0)]);
+    let money = probability.sample(&mut *rng);
+    if money > 0 {
+        stops.write(HitStop::freeze(80));
+    }
 
     event_writer.write(MoneyEvent {
-        money: probability.sample(&mut rng),
+        money,
         position: transform.translation().xy(),
     });
 }
@@ -399,7 +430,8 @@ pub fn lotto(
     FeatureSpawner::new::<Self>(),
     Tooltips::new::<Self>(),
     Points(10),
-    DebugCircle::color(FEATURE_RADIUS - 2., BLUE),
+    FeatureArt::new(6, FEATURE_SIZE - 4., BLUE),
+    BonkBurst("particles/bonk-splitter.ron"),
     Collider::circle(FEATURE_RADIUS - 2.)
 )]
 pub struct Splitter(usize);
@@ -416,6 +448,7 @@ pub fn splitter(
     filtered: Query<&FeatureCooldown<Splitter>>,
     mut transforms: Query<(&Splitter, &GlobalTransform)>,
     mut commands: Commands,
+    mut bursts: EventWriter<Burst>,
 ) {
     if filtered.contains(trigger.collider) {
         return;
@@ -463,6 +496,7 @@ pub fn splitter(
                 ),
             ));
         }
+        bursts.write(Burst::new("particles/split.ron", ball));
     }
 }
 
@@ -473,7 +507,8 @@ pub fn splitter(
     BonkImpulse(1.),
     FeatureSpawner::new::<Self>(),
     Tooltips::new::<Self>(),
-    DebugCircle::color(FEATURE_RADIUS - 2., MAROON),
+    FeatureArt::new(7, FEATURE_SIZE - 4., MAROON),
+    BonkBurst("particles/bonk-field-inverter.ron"),
     Collider::circle(FEATURE_RADIUS - 2.)
 )]
 pub struct FieldInverter;
@@ -40,6 +40,7 @@ impl FeatureGrid {
                 continue;
             }
 
+            let mut index = 0;
             let offset_local_x = (cols_potential.saturating_sub(1) as f32 * grid.spacing.x) * -0.5;
             let offset_local_y = (rows_potential.saturating_sub(1) as f32 * grid.spacing.y) * 0.5;
 
@@ -62,10 +63,11 @@ impl FeatureGrid {
                     {
                         commands.spawn((
                             ChildOf(grid_entity),
-                            FeatureSlot,
+                            FeatureSlot(index),
                             DebugCircle::new(4.0),
                             Transform::from_translation(final_pos.extend(0.0)),
                         ));
+                        index += 1;
                     }
                 }
             }
@@ -73,8 +75,11 @@ impl FeatureGrid {
     }
 }
 
+/// An empty position in the [`FeatureGrid`].
+///
+/// The index is stable for a given grid, so it can be used to restore a layout.
 #[derive(Component)]
-pub struct FeatureSlot;
+pub struct FeatureSlot(pub usize);
 
 #[derive(Component)]
 #[relationship(relationship_target = SlotFeature)]
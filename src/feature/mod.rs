@@ -5,14 +5,20 @@ use std::time::Duration;
 use crate::ball::{Ball, BallComponents, PaddleRestMult, PlayerBall};
 use crate::big::BigPoints;
 use crate::collectables::PointEvent;
+use crate::lights::FeatureFlare;
+use crate::music::{BeatPulse, CurrentTrack, MusicBeats};
+use crate::particles::Burst;
+use crate::shake::Trauma;
 use crate::state::{GameState, StateAppExt, remove_entities};
 use crate::{Avian, Layer, cabinet};
 use avian2d::prelude::PhysicsSet;
 use avian2d::prelude::*;
 use bevy::prelude::*;
+use bevy_light_2d::light::PointLight2d;
 
-use self::grid::FeatureGrid;
+use self::grid::{FeatureGrid, SlotFeatureOf};
 
+mod art;
 mod features;
 pub mod grid;
 
@@ -22,7 +28,7 @@ pub struct FeaturePlugin;
 
 impl Plugin for FeaturePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(FeaturesPlugin)
+        app.add_plugins((FeaturesPlugin, art::FeatureArtPlugin))
             .add_reset((
                 remove_entities::<With<Feature>>,
                 remove_entities::<With<FeatureGrid>>,
@@ -34,7 +40,9 @@ impl Plugin for FeaturePlugin {
             .add_systems(Avian, despawn_empty_bonks.before(PhysicsSet::Prepare))
             .add_observer(bonks)
             .add_observer(bonk_bounce)
-            .add_observer(feature_bonk);
+            .add_observer(bonk_burst)
+            .add_observer(feature_bonk)
+            .add_observer(feature_light);
     }
 }
 
@@ -122,9 +130,9 @@ fn feature_cooldown<T>(
 }
 
 /// The number of bonks before the feature despawns.
-#[derive(Clone, Component)]
+#[derive(Clone, Component, serde::Serialize, serde::Deserialize)]
 #[require(RigidBody::Kinematic, CollisionEventsEnabled)]
-enum Bonks {
+pub enum Bonks {
     Limited(usize),
     #[allow(unused)]
     Reloading {
@@ -156,11 +164,19 @@ fn bonks(
     }
 }
 
-fn despawn_empty_bonks(mut commands: Commands, bonks: Query<(Entity, &Bonks)>) {
-    for (entity, _) in bonks
+fn despawn_empty_bonks(
+    mut commands: Commands,
+    mut bursts: EventWriter<Burst>,
+    bonks: Query<(Entity, &Bonks, &GlobalTransform)>,
+) {
+    for (entity, _, transform) in bonks
         .iter()
-        .filter(|(_, bonks)| matches!(bonks, Bonks::Limited(bonks) if *bonks == 0))
+        .filter(|(_, bonks, _)| matches!(bonks, Bonks::Limited(bonks) if *bonks == 0))
     {
+        bursts.write(Burst::new(
+            "particles/feature-break.ron",
+            transform.translation().xy(),
+        ));
         commands.entity(entity).despawn();
     }
 }
@@ -170,10 +186,33 @@ fn despawn_empty_bonks(mut commands: Commands, bonks: Query<(Entity, &Bonks)>) {
 #[allow(unused)]
 struct BonksReload(Entity);
 
+/// Bonks within this many seconds of a beat are on the beat.
+pub const ON_BEAT_TOLERANCE: f32 = 0.07;
+
+/// Points multiplier for bonks on the beat.
+pub const ON_BEAT_MULT: f32 = 1.5;
+
 /// The factor applied to the impulse generated by a bonk.
 #[derive(Clone, Component)]
 struct BonkImpulse(f32);
 
+/// The particle effect a feature bursts with when a ball bonks it.
+#[derive(Clone, Copy, Component)]
+struct BonkBurst(&'static str);
+
+fn bonk_burst(
+    trigger: Trigger<OnCollisionStart>,
+    mut bursts: EventWriter<Burst>,
+    features: Query<&BonkBurst>,
+    balls: Query<&GlobalTransform, With<BallComponents>>,
+) {
+    if let (Ok(BonkBurst(effect)), Ok(ball)) =
+        (features.get(trigger.target()), balls.get(trigger.collider))
+    {
+        bursts.write(Burst::new(*effect, ball.translation().xy()));
+    }
+}
+
 fn feature_bonk(
     trigger: Trigger<OnCollisionStart>,
     mut bonk_writer: EventWriter<FeatureBonk>,
@@ -181,6 +220,7 @@ fn feature_bonk(
     features: Query<(&GlobalTransform, &Points), With<Feature>>,
     balls: Query<&BallComponents>,
     collider: Query<Option<&PaddleRestMult>>,
+    beats: Option<Single<&MusicBeats, With<CurrentTrack>>>,
 ) {
     let Ok((transform, Points(points))) = features.get(trigger.target()) else {
         return;
@@ -198,6 +238,9 @@ fn feature_bonk(
     if let Ok(Some(paddle_mult)) = collider.get(trigger.collider) {
         points *= 1. + paddle_mult.0;
     }
+    if beats.is_some_and(|beats| beats.on_beat(ON_BEAT_TOLERANCE)) {
+        points *= ON_BEAT_MULT;
+    }
 
     bonk_writer.write(FeatureBonk {
         feature: trigger.target(),
@@ -209,6 +252,19 @@ fn feature_bonk(
     });
 }
 
+/// Gives placed features a light that pulses with the music.
+fn feature_light(trigger: Trigger<OnAdd, SlotFeatureOf>, mut commands: Commands) {
+    commands.entity(trigger.target()).with_child((
+        BeatPulse::new(1.5),
+        FeatureFlare::default(),
+        PointLight2d {
+            radius: features::FEATURE_RADIUS * 3.,
+            falloff: 2.,
+            ..Default::default()
+        },
+    ));
+}
+
 #[derive(Component)]
 struct ImpulseGizmo {
     impulse: Vec2,
@@ -238,23 +294,36 @@ fn debug_impulse(
     }
 }
 
+/// Camera trauma per unit of [`BonkImpulse`] when the player's ball is bonked.
+///
+/// Bonks of other balls don't shake, or chain reactions would never stop.
+const BONK_TRAUMA: f32 = 0.08;
+
 fn bonk_bounce(
     trigger: Trigger<OnCollisionStart>,
     features: Query<(&GlobalTransform, &BonkImpulse), With<Feature>>,
-    mut balls: Query<(&GlobalTransform, &mut ExternalImpulse), Or<(With<Ball>, With<PlayerBall>)>>,
+    mut balls: Query<
+        (&GlobalTransform, &mut ExternalImpulse, Has<PlayerBall>),
+        Or<(With<Ball>, With<PlayerBall>)>,
+    >,
+    mut shake: EventWriter<Trauma>,
     mut commands: Commands,
 ) {
     match (
         features.get(trigger.target()),
         balls.get_mut(trigger.collider),
     ) {
-        (Ok((transform, mult)), Ok((ball_transform, mut bonk))) => {
+        (Ok((transform, mult)), Ok((ball_transform, mut bonk, player))) => {
             let ball_trans = ball_transform.translation().xy();
             let feature_trans = transform.translation().xy();
 
             let impulse = (ball_trans - feature_trans).normalize_or_zero() * 38_000. * mult.0;
             bonk.apply_impulse(impulse);
 
+            if player {
+                shake.write(Trauma(BONK_TRAUMA * mult.0));
+            }
+
             #[cfg(debug_assertions)]
             commands.spawn((
                 ImpulseGizmo {
@@ -0,0 +1,126 @@
+use bevy::ecs::component::HookContext;
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+use bevy_enhanced_input::events::Fired;
+
+use crate::input::{Enter, FocusDown, FocusLeft, FocusRight, FocusUp};
+
+pub struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Activate>()
+            .add_observer(focus_up)
+            .add_observer(focus_down)
+            .add_observer(focus_left)
+            .add_observer(focus_right)
+            .add_observer(activate);
+    }
+}
+
+/// Something the d-pad or arrow keys can move the focus onto.
+#[derive(Default, Component)]
+#[component(on_remove = Self::unfocus)]
+pub struct Focusable {
+    /// World position used to find neighbours when the entity's transform is not in world
+    /// space, like UI nodes.
+    pub anchor: Option<Vec2>,
+}
+
+impl Focusable {
+    pub fn at(anchor: Vec2) -> Self {
+        Self {
+            anchor: Some(anchor),
+        }
+    }
+
+    fn unfocus(mut world: DeferredWorld, ctx: HookContext) {
+        if world.get::<Focused>(ctx.entity).is_some() {
+            world.commands().entity(ctx.entity).try_remove::<Focused>();
+        }
+    }
+}
+
+/// Marks the one [`Focusable`] that has the focus. Screens draw their own highlight for it.
+#[derive(Component)]
+pub struct Focused;
+
+/// The focused entity was confirmed with South or Enter.
+#[derive(Event)]
+pub struct Activate(pub Entity);
+
+type Focusables<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Focusable,
+        &'static GlobalTransform,
+        Has<Focused>,
+    ),
+>;
+
+fn position(focusable: &Focusable, gt: &GlobalTransform) -> Vec2 {
+    focusable.anchor.unwrap_or_else(|| gt.translation().xy())
+}
+
+/// Moves the focus to the nearest [`Focusable`] in `direction`, favouring ones in line with
+/// the current focus. With nothing focused yet, the top left one is picked.
+fn move_focus(mut commands: Commands, focusables: Focusables, direction: Vec2) {
+    let current = focusables
+        .iter()
+        .find(|(.., focused)| *focused)
+        .map(|(entity, focusable, gt, _)| (entity, position(focusable, gt)));
+
+    let next = match current {
+        Some((_, from)) => focusables
+            .iter()
+            .filter_map(|(entity, focusable, gt, _)| {
+                let offset = position(focusable, gt) - from;
+                let along = offset.dot(direction);
+                (along > 1.).then(|| (entity, along + offset.perp_dot(direction).abs() * 2.))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(entity, _)| entity),
+        None => focusables
+            .iter()
+            .map(|(entity, focusable, gt, _)| (entity, position(focusable, gt)))
+            .min_by(|a, b| b.1.y.total_cmp(&a.1.y).then(a.1.x.total_cmp(&b.1.x)))
+            .map(|(entity, _)| entity),
+    };
+
+    let Some(next) = next else {
+        return;
+    };
+
+    if let Some((entity, _)) = current {
+        commands.entity(entity).remove::<Focused>();
+    }
+    commands.entity(next).insert(Focused);
+}
+
+fn focus_up(_: Trigger<Fired<FocusUp>>, commands: Commands, focusables: Focusables) {
+    move_focus(commands, focusables, Vec2::Y);
+}
+
+fn focus_down(_: Trigger<Fired<FocusDown>>, commands: Commands, focusables: Focusables) {
+    move_focus(commands, focusables, Vec2::NEG_Y);
+}
+
+fn focus_left(_: Trigger<Fired<FocusLeft>>, commands: Commands, focusables: Focusables) {
+    move_focus(commands, focusables, Vec2::NEG_X);
+}
+
+fn focus_right(_: Trigger<Fired<FocusRight>>, commands: Commands, focusables: Focusables) {
+    move_focus(commands, focusables, Vec2::X);
+}
+
+fn activate(
+    _: Trigger<Fired<Enter>>,
+    focused: Option<Single<Entity, (With<Focused>, With<Focusable>)>>,
+    mut writer: EventWriter<Activate>,
+) {
+    if let Some(entity) = focused {
+        writer.write(Activate(*entity));
+    }
+}
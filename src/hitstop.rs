@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+
+use crate::tween::PhysicsTimeMult;
+
+pub struct HitStopPlugin;
+
+impl Plugin for HitStopPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<HitStop>()
+            .init_resource::<HitStops>()
+            .add_systems(Update, apply_hit_stops);
+    }
+}
+
+/// Freezes or slows physics for a moment to sell a big event.
+///
+/// Only physics is scaled, so virtual time, and every gameplay timer running on it,
+/// keeps its pace.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct HitStop {
+    /// Physics speed while the stop lasts, 0 being frozen.
+    scale: f32,
+    /// Length of the stop in real seconds, so it isn't stretched by its own slow down.
+    secs: f32,
+}
+
+impl HitStop {
+    pub fn freeze(millis: u64) -> Self {
+        Self::slow(0., millis)
+    }
+
+    pub fn slow(scale: f32, millis: u64) -> Self {
+        Self {
+            scale,
+            secs: millis as f32 / 1000.,
+        }
+    }
+}
+
+/// Stops that are still running. Overlapping stops each keep their own timer and the
+/// slowest one wins, so a short freeze inside a long slow down resumes the slow down.
+#[derive(Default, Resource)]
+struct HitStops(Vec<(f32, Timer)>);
+
+impl HitStops {
+    fn scale(&self) -> f32 {
+        self.0.iter().map(|(scale, _)| *scale).fold(1., f32::min)
+    }
+}
+
+fn apply_hit_stops(
+    time: Res<Time<Real>>,
+    mut stops: ResMut<HitStops>,
+    mut reader: EventReader<HitStop>,
+    mut mult: ResMut<PhysicsTimeMult>,
+) {
+    for stop in stops.0.iter_mut() {
+        stop.1.tick(time.delta());
+    }
+    stops.0.retain(|(_, timer)| !timer.finished());
+
+    for stop in reader.read() {
+        stops.0.push((
+            stop.scale.clamp(0., 1.),
+            Timer::from_seconds(stop.secs, TimerMode::Once),
+        ));
+    }
+
+    let scale = stops.scale();
+    if mult.0 != scale {
+        mult.0 = scale;
+    }
+}
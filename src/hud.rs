@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
+
+use crate::accessibility::ScaledText;
+use crate::ball::{BallComponents, PaddleRestMult, PlayerBall};
+use crate::cabinet::{self, Cabinet, UIZ};
+use crate::collectables::{HexColor, POINT_COLOR, Points};
+use crate::feature::{ON_BEAT_MULT, ON_BEAT_TOLERANCE};
+use crate::music::{CurrentTrack, MusicBeats};
+use crate::stage::Stage;
+use crate::state::{GameState, Playing};
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::StartGame), spawn_hud)
+            .add_systems(
+                Update,
+                (stage_ui, progress_ui, balls_ui, mults_ui).in_set(Playing),
+            );
+    }
+}
+
+#[derive(Component)]
+struct StageUI;
+
+#[derive(Component)]
+struct BallsUI;
+
+#[derive(Component)]
+struct ProgressUI;
+
+#[derive(Component)]
+struct MultsUI;
+
+const BAR_WIDTH: f32 = 300.;
+const BAR_HEIGHT: f32 = 8.;
+const BAR_COLOR: HexColor = HexColor(0x2a2530);
+/// How quickly the bar catches up with the points, per second.
+const BAR_RATE: f32 = 6.;
+
+fn spawn_hud(mut commands: Commands, server: Res<AssetServer>) {
+    let font = |size: f32| {
+        (
+            TextFont {
+                font: server.load("fonts/cube.ttf"),
+                font_size: size,
+                ..Default::default()
+            },
+            ScaledText(size),
+        )
+    };
+    let left = -349. / 2. + 25.;
+    let right = 349. / 2. - 25.;
+    let top = 115. / 2. - 25.;
+
+    commands.spawn((
+        Cabinet,
+        HIGH_RES_LAYER,
+        Transform::from_scale(Vec3::splat(crate::RESOLUTION_SCALE)).with_translation(Vec3::new(
+            cabinet::WIDTH / 1.1,
+            cabinet::HEIGHT / 2.5 - 250.,
+            UIZ,
+        )),
+        Sprite::from_image(server.load("textures/panel.png")),
+        children![
+            (
+                StageUI,
+                Text2d::default(),
+                font(25.),
+                Anchor::TopLeft,
+                Transform::from_xyz(left, top, 1.),
+            ),
+            (
+                BallsUI,
+                Text2d::default(),
+                font(18.),
+                Anchor::TopRight,
+                Transform::from_xyz(right, top, 1.),
+            ),
+            (
+                Sprite {
+                    anchor: Anchor::CenterLeft,
+                    ..Sprite::from_color(BAR_COLOR, Vec2::new(BAR_WIDTH, BAR_HEIGHT))
+                },
+                Transform::from_xyz(left, 0., 1.),
+            ),
+            (
+                ProgressUI,
+                Sprite {
+                    anchor: Anchor::CenterLeft,
+                    ..Sprite::from_color(POINT_COLOR, Vec2::new(0., BAR_HEIGHT))
+                },
+                Transform::from_xyz(left, 0., 2.),
+            ),
+            (
+                MultsUI,
+                Text2d::default(),
+                font(18.),
+                TextColor(POINT_COLOR.into()),
+                Anchor::BottomLeft,
+                Transform::from_xyz(left, -top, 1.),
+            ),
+        ],
+    ));
+}
+
+fn stage_ui(mut text: Single<&mut Text2d, With<StageUI>>, stage: Single<&Stage, Changed<Stage>>) {
+    text.0 = format!("STAGE {}", stage.level + 1);
+}
+
+fn progress_ui(
+    time: Res<Time>,
+    mut bar: Single<&mut Sprite, With<ProgressUI>>,
+    points: Res<Points>,
+    stage: Single<&Stage>,
+) {
+    let goal = points.get().share(&stage.points).min(1.) * BAR_WIDTH;
+    let Some(size) = bar.custom_size.as_mut() else {
+        return;
+    };
+
+    // Empties at once on a new stage, fills smoothly otherwise.
+    if goal < size.x {
+        size.x = goal;
+    } else {
+        size.x += (goal - size.x) * (BAR_RATE * time.delta_secs()).min(1.);
+    }
+}
+
+/// Balls left to launch this stage and balls in play.
+fn balls_ui(
+    mut text: Single<&mut Text2d, With<BallsUI>>,
+    stage: Single<&Stage>,
+    balls: Query<(), With<BallComponents>>,
+) {
+    let value = format!("BALLS {}  LIVE {}", stage.lives, balls.iter().count());
+    if text.0 != value {
+        text.0 = value;
+    }
+}
+
+/// Lists the multipliers the next bonk would get.
+fn mults_ui(
+    mut text: Single<&mut Text2d, With<MultsUI>>,
+    paddle: Option<Single<&PaddleRestMult, With<PlayerBall>>>,
+    beats: Option<Single<&MusicBeats, With<CurrentTrack>>>,
+) {
+    let mut mults = Vec::new();
+    if let Some(paddle) = paddle.filter(|paddle| paddle.0 > 0.) {
+        mults.push(format!("PADDLE x{:.1}", 1. + paddle.0));
+    }
+    if beats.is_some_and(|beats| beats.on_beat(ON_BEAT_TOLERANCE)) {
+        mults.push(format!("BEAT x{ON_BEAT_MULT}"));
+    }
+
+    let value = mults.join("  ");
+    if text.0 != value {
+        text.0 = value;
+    }
+}
@@ -1,6 +1,10 @@
+use std::collections::BTreeMap;
+
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
+use bevy_persistent::prelude::*;
 
+use crate::pause::PauseState;
 use crate::state::GameState;
 
 pub struct InputPlugin;
@@ -9,12 +13,187 @@ impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app.add_input_context::<ActivePlay>()
             .add_input_context::<Menu>()
+            .add_input_context::<InitialsEntry>()
+            .add_systems(PreStartup, controls)
             .add_systems(Update, action_ctx)
             .add_observer(bind_active)
-            .add_observer(bind_menu);
+            .add_observer(bind_menu)
+            .add_observer(bind_initials);
+    }
+}
+
+/// Every rebindable action, in the order they are listed on the controls screen.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub enum Control {
+    Paddle,
+    DepleteBall,
+    Pause,
+    Confirm,
+    Back,
+    Up,
+    Down,
+    Left,
+    Right,
+    SwitchTab,
+    Reset,
+    SandboxBall,
+}
+
+impl Control {
+    pub const ALL: [Self; 12] = [
+        Self::Paddle,
+        Self::DepleteBall,
+        Self::Pause,
+        Self::Confirm,
+        Self::Back,
+        Self::Up,
+        Self::Down,
+        Self::Left,
+        Self::Right,
+        Self::SwitchTab,
+        Self::Reset,
+        Self::SandboxBall,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Paddle => "Paddle",
+            Self::DepleteBall => "Deplete ball",
+            Self::Pause => "Pause",
+            Self::Confirm => "Confirm",
+            Self::Back => "Back",
+            Self::Up => "Up",
+            Self::Down => "Down",
+            Self::Left => "Left",
+            Self::Right => "Right",
+            Self::SwitchTab => "Switch tab",
+            Self::Reset => "Reset run",
+            Self::SandboxBall => "Sandbox ball",
+        }
+    }
+
+    fn defaults(&self) -> Vec<InputButton> {
+        use InputButton::{Gamepad, Key, Mouse};
+
+        match self {
+            Self::Paddle => vec![Key(KeyCode::Space), Gamepad(GamepadButton::South)],
+            Self::DepleteBall => vec![Key(KeyCode::KeyD), Gamepad(GamepadButton::West)],
+            Self::Pause => vec![Key(KeyCode::Escape), Gamepad(GamepadButton::Start)],
+            Self::Confirm => vec![
+                Key(KeyCode::Space),
+                Key(KeyCode::Enter),
+                Gamepad(GamepadButton::South),
+            ],
+            Self::Back => vec![
+                Key(KeyCode::Escape),
+                Key(KeyCode::Backspace),
+                Gamepad(GamepadButton::East),
+            ],
+            Self::Up => vec![Key(KeyCode::ArrowUp), Gamepad(GamepadButton::DPadUp)],
+            Self::Down => vec![Key(KeyCode::ArrowDown), Gamepad(GamepadButton::DPadDown)],
+            Self::Left => vec![Key(KeyCode::ArrowLeft), Gamepad(GamepadButton::DPadLeft)],
+            Self::Right => vec![Key(KeyCode::ArrowRight), Gamepad(GamepadButton::DPadRight)],
+            Self::SwitchTab => vec![
+                Key(KeyCode::ArrowLeft),
+                Key(KeyCode::ArrowRight),
+                Key(KeyCode::Tab),
+                Gamepad(GamepadButton::LeftTrigger),
+                Gamepad(GamepadButton::RightTrigger),
+            ],
+            Self::Reset => vec![Key(KeyCode::KeyR)],
+            Self::SandboxBall => vec![Mouse(MouseButton::Right)],
+        }
+    }
+}
+
+/// A single physical button an action can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum InputButton {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+}
+
+impl InputButton {
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, Self::Gamepad(_))
+    }
+
+    fn input(self) -> Input {
+        match self {
+            Self::Key(key) => key.into(),
+            Self::Mouse(button) => button.into(),
+            Self::Gamepad(button) => button.into(),
+        }
+    }
+}
+
+impl core::fmt::Display for InputButton {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Key(key) => {
+                let name = format!("{key:?}");
+                write!(f, "{}", name.strip_prefix("Key").unwrap_or(&name))
+            }
+            Self::Mouse(button) => write!(f, "Mouse {button:?}"),
+            Self::Gamepad(button) => write!(f, "Pad {button:?}"),
+        }
+    }
+}
+
+/// The player's bindings, saved to `controls.ron` next to the player data.
+///
+/// Actions missing from the file fall back to [`Control::defaults`].
+#[derive(Default, Resource, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Controls {
+    bindings: BTreeMap<Control, Vec<InputButton>>,
+}
+
+impl Controls {
+    pub fn get(&self, control: Control) -> Vec<InputButton> {
+        self.bindings
+            .get(&control)
+            .cloned()
+            .unwrap_or_else(|| control.defaults())
+    }
+
+    pub fn set(&mut self, control: Control, buttons: Vec<InputButton>) {
+        self.bindings.insert(control, buttons);
+    }
+
+    pub fn reset(&mut self) {
+        self.bindings.clear();
+    }
+
+    fn bind<'a, C: InputContext, A: InputAction>(
+        &self,
+        actions: &'a mut Actions<C>,
+        control: Control,
+    ) -> &'a mut ActionBinding {
+        let binding = actions.bind::<A>();
+        for button in self.get(control) {
+            binding.to(button.input());
+        }
+        binding
     }
 }
 
+fn controls(mut commands: Commands) {
+    commands.insert_resource(
+        Persistent::<Controls>::builder()
+            .name("controls")
+            .format(StorageFormat::Ron)
+            .path(crate::save::save_dir().join("controls.ron"))
+            .default(Default::default())
+            .revert_to_default_on_deserialization_errors(true)
+            .build()
+            .unwrap(),
+    )
+}
+
 #[derive(InputContext)]
 pub struct ActivePlay;
 
@@ -26,34 +205,157 @@ pub struct PaddleUp;
 #[input_action(output = bool)]
 pub struct PaddleDown;
 
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct DepleteBall;
+
+/// Requires a reset so the press that resumed a run doesn't pause it again.
+#[derive(Debug, InputAction)]
+#[input_action(output = bool, require_reset = true)]
+pub struct Pause;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct ResetRun;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct SandboxBall;
+
 fn bind_active(
     trigger: Trigger<Binding<ActivePlay>>,
     mut actions: Query<&mut Actions<ActivePlay>>,
+    controls: Res<Persistent<Controls>>,
 ) {
     let mut actions = actions.get_mut(trigger.target()).unwrap();
-    actions
-        .bind::<PaddleUp>()
-        .to((KeyCode::Space, GamepadButton::South))
+    controls
+        .bind::<_, PaddleUp>(&mut actions, Control::Paddle)
         .with_conditions(JustPress::new(1.0));
-
-    actions
-        .bind::<PaddleDown>()
-        .to((KeyCode::Space, GamepadButton::South))
+    controls
+        .bind::<_, PaddleDown>(&mut actions, Control::Paddle)
         .with_conditions(Release::new(1.0));
+    controls
+        .bind::<_, DepleteBall>(&mut actions, Control::DepleteBall)
+        .with_conditions(JustPress::new(1.0));
+    controls
+        .bind::<_, Pause>(&mut actions, Control::Pause)
+        .with_conditions(JustPress::new(1.0));
+    controls
+        .bind::<_, ResetRun>(&mut actions, Control::Reset)
+        .with_conditions(JustPress::new(1.0));
+    controls
+        .bind::<_, SandboxBall>(&mut actions, Control::SandboxBall)
+        .with_conditions(JustPress::new(1.0));
 }
 
 #[derive(InputContext)]
 pub struct Menu;
 
+/// Requires a reset so a press carried over from another context is ignored.
 #[derive(Debug, InputAction)]
-#[input_action(output = bool)]
+#[input_action(output = bool, require_reset = true)]
 pub struct Enter;
 
-fn bind_menu(trigger: Trigger<Binding<Menu>>, mut actions: Query<&mut Actions<Menu>>) {
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct SwitchTab;
+
+/// Requires a reset so the press that paused a run doesn't resume it.
+#[derive(Debug, InputAction)]
+#[input_action(output = bool, require_reset = true)]
+pub struct Back;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct FocusUp;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct FocusDown;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct FocusLeft;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct FocusRight;
+
+fn bind_menu(
+    trigger: Trigger<Binding<Menu>>,
+    mut actions: Query<&mut Actions<Menu>>,
+    controls: Res<Persistent<Controls>>,
+) {
     let mut actions = actions.get_mut(trigger.target()).unwrap();
-    actions
-        .bind::<Enter>()
-        .to((KeyCode::Space, KeyCode::Enter, GamepadButton::South));
+    controls
+        .bind::<_, Enter>(&mut actions, Control::Confirm)
+        .with_conditions(JustPress::new(1.0));
+    controls
+        .bind::<_, SwitchTab>(&mut actions, Control::SwitchTab)
+        .with_conditions(JustPress::new(1.0));
+    controls
+        .bind::<_, Back>(&mut actions, Control::Back)
+        .with_conditions(JustPress::new(1.0));
+    controls
+        .bind::<_, FocusUp>(&mut actions, Control::Up)
+        .with_conditions(JustPress::new(1.0));
+    controls
+        .bind::<_, FocusDown>(&mut actions, Control::Down)
+        .with_conditions(JustPress::new(1.0));
+    controls
+        .bind::<_, FocusLeft>(&mut actions, Control::Left)
+        .with_conditions(JustPress::new(1.0));
+    controls
+        .bind::<_, FocusRight>(&mut actions, Control::Right)
+        .with_conditions(JustPress::new(1.0));
+}
+
+#[derive(InputContext)]
+pub struct InitialsEntry;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct LetterUp;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct LetterDown;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct LetterLeft;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct LetterRight;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct ConfirmInitials;
+
+fn bind_initials(
+    trigger: Trigger<Binding<InitialsEntry>>,
+    mut actions: Query<&mut Actions<InitialsEntry>>,
+    controls: Res<Persistent<Controls>>,
+) {
+    let mut actions = actions.get_mut(trigger.target()).unwrap();
+    controls
+        .bind::<_, LetterUp>(&mut actions, Control::Up)
+        .with_conditions(JustPress::new(1.0));
+    controls
+        .bind::<_, LetterDown>(&mut actions, Control::Down)
+        .with_conditions(JustPress::new(1.0));
+    controls
+        .bind::<_, LetterLeft>(&mut actions, Control::Left)
+        // Erasing a typed letter is not a rebindable action.
+        .to(KeyCode::Backspace)
+        .with_conditions(JustPress::new(1.0));
+    controls
+        .bind::<_, LetterRight>(&mut actions, Control::Right)
+        .with_conditions(JustPress::new(1.0));
+    controls
+        .bind::<_, ConfirmInitials>(&mut actions, Control::Confirm)
+        .with_conditions(JustPress::new(1.0));
 }
 
 fn action_ctx(
@@ -61,10 +363,42 @@ fn action_ctx(
     state: Res<State<GameState>>,
     active: Option<Single<Entity, With<Actions<ActivePlay>>>>,
     menu: Option<Single<Entity, With<Actions<Menu>>>>,
+    initials: Option<Single<Entity, With<Actions<InitialsEntry>>>>,
+    controls: Res<Persistent<Controls>>,
+    pause: Res<State<PauseState>>,
 ) {
-    if state.is_changed() || state.is_added() {
-        match state.get() {
-            GameState::Menu | GameState::Leaderboard => {
+    // Contexts only read their bindings when they are spawned.
+    if controls.is_changed() && !controls.is_added() && !state.is_changed() {
+        if let Some(entity) = active {
+            commands.entity(*entity).despawn();
+            commands.spawn(Actions::<ActivePlay>::default());
+        }
+        if let Some(entity) = menu {
+            commands.entity(*entity).despawn();
+            commands.spawn(Actions::<Menu>::default());
+        }
+        if let Some(entity) = initials {
+            commands.entity(*entity).despawn();
+            commands.spawn(Actions::<InitialsEntry>::default());
+        }
+        return;
+    }
+
+    if state.is_changed() || state.is_added() || pause.is_changed() {
+        if let Some(entity) = initials {
+            commands.entity(*entity).despawn();
+        }
+
+        match (state.get(), pause.get()) {
+            (
+                GameState::Menu
+                | GameState::Leaderboard
+                | GameState::Achievements
+                | GameState::Controls
+                | GameState::Selection,
+                _,
+            )
+            | (_, PauseState::Paused) => {
                 if let Some(entity) = active {
                     commands.entity(*entity).despawn();
                 }
@@ -73,7 +407,7 @@ fn action_ctx(
                     commands.spawn(Actions::<Menu>::default());
                 }
             }
-            GameState::Selection => {
+            (GameState::Initials, _) => {
                 if let Some(entity) = active {
                     commands.entity(*entity).despawn();
                 }
@@ -81,6 +415,8 @@ fn action_ctx(
                 if let Some(entity) = menu {
                     commands.entity(*entity).despawn();
                 }
+
+                commands.spawn(Actions::<InitialsEntry>::default());
             }
             _ => {
                 if let Some(entity) = menu {
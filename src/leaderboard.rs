@@ -1,76 +1,360 @@
 use bevy::image::{
     ImageAddressMode, ImageFilterMode, ImageLoaderSettings, ImageSampler, ImageSamplerDescriptor,
 };
+use bevy::input::keyboard::{Key, KeyboardInput};
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 use bevy_enhanced_input::events::Fired;
 use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
-use bevy_persistent::prelude::*;
 
 use crate::big::BigPoints;
-use crate::cabinet::{ScrollingTexture, Speed};
-use crate::collectables::TotalPoints;
-use crate::input::Enter;
-use crate::stage::{Advance, Loose, Stage, Win};
-use crate::state::{GameState, StateAppExt, remove_entities};
+use crate::cabinet::{BackgroundState, ScrollingTexture, Speed};
+use crate::collectables::{POINT_COLOR, TotalPoints};
+use crate::feature::grid::SlotFeatureOf;
+use crate::focus::Focused;
+use crate::input::{
+    ConfirmInitials, Enter, LetterDown, LetterLeft, LetterRight, LetterUp, SwitchTab,
+};
+use crate::online::GlobalTop;
+use crate::rng::RunRng;
+use crate::save::{PlayerData, PlayerSave};
+use crate::stage::{Loose, Stage, Win};
+use crate::state::{GameMode, GameState, StateAppExt, remove_entities};
+use crate::text::TextFlash;
+use crate::tooltips::Tooltips;
+use crate::touch::Taps;
+use crate::transition::{GoTo, Transition};
+
+pub const LEADERBOARD_SIZE: usize = 10;
 
 pub struct LeaderBoardPlugin;
 
 impl Plugin for LeaderBoardPlugin {
     fn build(&self, app: &mut App) {
         app.add_reset(remove_entities::<With<Leaderboard>>)
-            .add_systems(Startup, player_data)
+            .insert_resource(NewEntry::default())
+            .insert_resource(LeaderboardTab::default())
+            .add_event::<RunRecorded>()
+            .add_systems(OnEnter(GameState::Initials), begin_initials)
+            .add_systems(
+                Update,
+                (type_initials, tap_initials, update_initials)
+                    .chain()
+                    .in_set(crate::state::Initials),
+            )
+            .add_systems(
+                OnExit(GameState::Initials),
+                remove_entities::<With<InitialsEntry>>,
+            )
             .add_systems(
                 OnEnter(GameState::Leaderboard),
                 (spawn_leaderboard, background),
             )
-            .add_observer(|_: Trigger<Fired<Enter>>, mut commands: Commands| {
-                commands.run_system_cached(remove_entities::<With<Leaderboard>>);
-                commands.set_state(GameState::ToGame);
-            })
-            .add_observer(update_points::<Win>)
-            .add_observer(update_points::<Loose>)
-            .add_observer(update_points::<Advance>);
+            .add_systems(
+                Update,
+                (tap_leaderboard, spawn_rows)
+                    .chain()
+                    .in_set(crate::state::Leaderboard),
+            )
+            .add_systems(
+                OnExit(GameState::Leaderboard),
+                |mut new: ResMut<NewEntry>| {
+                    new.0 = None;
+                },
+            )
+            .add_observer(
+                |_: Trigger<Fired<Enter>>,
+                 mut commands: Commands,
+                 state: Res<State<GameState>>,
+                 focused: Query<(), With<Focused>>| {
+                    // A focused menu item handles the press itself.
+                    if matches!(state.get(), GameState::Menu | GameState::Leaderboard)
+                        && focused.is_empty()
+                    {
+                        commands.run_system_cached(remove_entities::<With<Leaderboard>>);
+                        commands.send_event(GoTo::new(GameState::StartGame, Transition::slider()));
+                    }
+                },
+            )
+            .add_observer(letter_up)
+            .add_observer(letter_down)
+            .add_observer(letter_left)
+            .add_observer(letter_right)
+            .add_observer(confirm_initials)
+            .add_observer(switch_tab)
+            .add_observer(record_run::<Win>)
+            .add_observer(record_run::<Loose>);
     }
 }
 
-#[derive(Default, serde::Serialize, serde::Deserialize, Resource)]
-struct PlayerData {
-    /// (level, points)
-    point_record: Vec<(usize, BigPoints)>,
-}
-
-fn player_data(mut commands: Commands) {
-    let element = "slugball-player-data";
-    let config_dir = dirs::config_dir()
-        .map(|native_config_dir| native_config_dir.join(element))
-        .unwrap_or_else(|| std::path::Path::new("local").join(element));
-
-    commands.insert_resource(
-        Persistent::<PlayerData>::builder()
-            .name("player data")
-            .format(StorageFormat::Bincode)
-            .path(config_dir.join("data"))
-            .default(Default::default())
-            .revertible(true)
-            .revert_to_default_on_deserialization_errors(true)
-            .build()
-            .unwrap(),
-    )
+impl PlayerData {
+    fn qualifies(&self, points: &BigPoints) -> bool {
+        self.point_record.len() < LEADERBOARD_SIZE
+            || self
+                .point_record
+                .get(LEADERBOARD_SIZE - 1)
+                .is_some_and(|entry| points.0 > entry.points.0)
+    }
+
+    /// Inserts `entry` in score order and returns its rank.
+    fn insert(&mut self, entry: LeaderboardEntry) -> usize {
+        let rank = self
+            .point_record
+            .partition_point(|other| other.points.0 >= entry.points.0);
+        self.point_record.insert(rank, entry);
+        rank
+    }
 }
 
-fn update_points<E: Component>(
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LeaderboardEntry {
+    pub initials: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub seed: u64,
+    pub level: usize,
+    pub points: BigPoints,
+    /// Names of the features on the board when the run ended.
+    pub features: Vec<String>,
+    pub mode: GameMode,
+}
+
+/// Written whenever a finished run is committed to [`PlayerData`].
+#[derive(Event)]
+pub struct RunRecorded(pub LeaderboardEntry);
+
+/// A finished run waiting for initials before it is written to [`PlayerData`].
+#[derive(Resource)]
+struct PendingEntry(LeaderboardEntry);
+
+/// Rank of the entry recorded during this visit to the leaderboard.
+#[derive(Default, Resource)]
+struct NewEntry(Option<usize>);
+
+fn record_run<E: Component>(
     _: Trigger<OnAdd, E>,
+    mut commands: Commands,
     stage: Single<&Stage>,
-    mut data: ResMut<Persistent<PlayerData>>,
     total_points: Res<TotalPoints>,
+    rng: Res<RunRng>,
+    features: Query<&Tooltips, With<SlotFeatureOf>>,
 ) {
     info!("recording: {}", total_points.get().clone());
-    data.point_record
-        .push((stage.level.saturating_sub(1), total_points.get().clone()));
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    commands.insert_resource(PendingEntry(LeaderboardEntry {
+        initials: String::from("---"),
+        timestamp,
+        seed: rng.seed(),
+        level: stage.level.saturating_sub(1),
+        points: total_points.get().clone(),
+        features: features.iter().map(|tips| tips.name.to_string()).collect(),
+        mode: GameMode::current(),
+    }));
+}
+
+fn commit_entry(
+    data: &mut PlayerSave,
+    writer: &mut EventWriter<RunRecorded>,
+    entry: LeaderboardEntry,
+) -> usize {
+    writer.write(RunRecorded(entry.clone()));
+    let rank = data.insert(entry);
     if let Err(e) = data.persist() {
         error!("failed to save player data: {e}");
     }
+    rank
+}
+
+#[derive(Component)]
+struct InitialsEntry {
+    letters: [u8; 3],
+    cursor: usize,
+}
+
+#[derive(Component)]
+struct InitialSlot(usize);
+
+fn begin_initials(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    pending: Option<Res<PendingEntry>>,
+    mut data: ResMut<PlayerSave>,
+    mut new: ResMut<NewEntry>,
+    mut writer: EventWriter<RunRecorded>,
+) {
+    let Some(pending) = pending else {
+        commands.set_state(GameState::Leaderboard);
+        return;
+    };
+
+    if !data.qualifies(&pending.0.points) {
+        new.0 = None;
+        commit_entry(&mut data, &mut writer, pending.0.clone());
+        commands.remove_resource::<PendingEntry>();
+        commands.set_state(GameState::Leaderboard);
+        return;
+    }
+
+    commands
+        .spawn((
+            InitialsEntry {
+                letters: *b"AAA",
+                cursor: 0,
+            },
+            Transform::from_xyz(0., 0., LEADERZ),
+            Visibility::Visible,
+            HIGH_RES_LAYER,
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Text2d::new("NEW HIGH SCORE"),
+                TextFont {
+                    font_size: 54.,
+                    font: server.load("fonts/saiba.ttf"),
+                    ..Default::default()
+                },
+                HIGH_RES_LAYER,
+                Transform::from_xyz(0., crate::RES_HEIGHT / 3., 0.),
+            ));
+            root.spawn((
+                Text2d::new(format!("{}", pending.0.points)),
+                TextFont {
+                    font_size: 32.,
+                    font: server.load("fonts/cube.ttf"),
+                    ..Default::default()
+                },
+                TextColor(POINT_COLOR.into()),
+                HIGH_RES_LAYER,
+                Transform::from_xyz(0., crate::RES_HEIGHT / 3. - 80., 0.),
+            ));
+
+            for i in 0..3 {
+                root.spawn((
+                    InitialSlot(i),
+                    Text2d::default(),
+                    TextFont {
+                        font_size: 72.,
+                        font: server.load("fonts/cube.ttf"),
+                        ..Default::default()
+                    },
+                    HIGH_RES_LAYER,
+                    Transform::from_xyz((i as f32 - 1.) * 80., 0., 0.),
+                ));
+            }
+        });
+}
+
+fn type_initials(mut reader: EventReader<KeyboardInput>, mut entry: Single<&mut InitialsEntry>) {
+    for event in reader.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+
+        let Key::Character(chars) = &event.logical_key else {
+            continue;
+        };
+
+        if let Some(c) = chars
+            .chars()
+            .next()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_uppercase())
+        {
+            let cursor = entry.cursor;
+            entry.letters[cursor] = c as u8;
+            entry.cursor = (cursor + 1).min(2);
+        }
+    }
+}
+
+fn update_initials(
+    entry: Single<&InitialsEntry, Changed<InitialsEntry>>,
+    mut slots: Query<(&InitialSlot, &mut Text2d, &mut TextColor)>,
+) {
+    for (slot, mut text, mut color) in slots.iter_mut() {
+        text.0 = (entry.letters[slot.0] as char).to_string();
+        color.0 = if slot.0 == entry.cursor {
+            POINT_COLOR.into()
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+/// Tapping a letter selects and advances it, tapping anywhere else confirms.
+fn tap_initials(mut commands: Commands, mut taps: Taps, mut entry: Single<&mut InitialsEntry>) {
+    for tap in taps.read() {
+        let slot = (0..3).find(|i| {
+            let position = Vec2::new((*i as f32 - 1.) * 80., 0.);
+            (tap - position).abs().cmplt(Vec2::splat(40.)).all()
+        });
+
+        match slot {
+            Some(slot) => {
+                entry.cursor = slot;
+                entry.letters[slot] = cycle_letter(entry.letters[slot], 1);
+            }
+            None => commands.run_system_cached(commit_initials),
+        }
+    }
+}
+
+const INITIALS_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+fn cycle_letter(letter: u8, offset: isize) -> u8 {
+    let len = INITIALS_ALPHABET.len() as isize;
+    let index = INITIALS_ALPHABET
+        .iter()
+        .position(|l| *l == letter)
+        .unwrap_or_default() as isize;
+    INITIALS_ALPHABET[(index + offset).rem_euclid(len) as usize]
+}
+
+fn letter_up(_: Trigger<Fired<LetterUp>>, mut entry: Single<&mut InitialsEntry>) {
+    let cursor = entry.cursor;
+    entry.letters[cursor] = cycle_letter(entry.letters[cursor], 1);
+}
+
+fn letter_down(_: Trigger<Fired<LetterDown>>, mut entry: Single<&mut InitialsEntry>) {
+    let cursor = entry.cursor;
+    entry.letters[cursor] = cycle_letter(entry.letters[cursor], -1);
+}
+
+fn letter_left(_: Trigger<Fired<LetterLeft>>, mut entry: Single<&mut InitialsEntry>) {
+    entry.cursor = entry.cursor.saturating_sub(1);
+}
+
+fn letter_right(_: Trigger<Fired<LetterRight>>, mut entry: Single<&mut InitialsEntry>) {
+    entry.cursor = (entry.cursor + 1).min(2);
+}
+
+fn confirm_initials(_: Trigger<Fired<ConfirmInitials>>, mut commands: Commands) {
+    commands.run_system_cached(commit_initials);
+}
+
+fn commit_initials(
+    mut commands: Commands,
+    entry: Single<&InitialsEntry>,
+    pending: Option<Res<PendingEntry>>,
+    mut data: ResMut<PlayerSave>,
+    mut new: ResMut<NewEntry>,
+    mut writer: EventWriter<RunRecorded>,
+) {
+    let Some(pending) = pending else {
+        return;
+    };
+
+    let mut record = pending.0.clone();
+    record.initials = String::from_utf8_lossy(&entry.letters).into_owned();
+    new.0 = Some(commit_entry(&mut data, &mut writer, record));
+
+    commands.remove_resource::<PendingEntry>();
+    commands.send_event(GoTo::new(GameState::Leaderboard, Transition::dissolve()));
 }
 
 #[derive(Component)]
@@ -84,13 +368,22 @@ fn background(
     mut meshes: ResMut<Assets<Mesh>>,
     mut mats: ResMut<Assets<ScrollingTexture>>,
 ) {
-    commands.spawn((
-        Leaderboard,
+    commands.spawn((Leaderboard, checkers(&server, &mut meshes, &mut mats)));
+}
+
+/// Scrolling checkerboard behind full screen menus.
+pub fn checkers(
+    server: &AssetServer,
+    meshes: &mut Assets<Mesh>,
+    mats: &mut Assets<ScrollingTexture>,
+) -> impl Bundle {
+    (
         HIGH_RES_LAYER,
         Mesh2d(meshes.add(Rectangle::new(1024., 1024.))),
         Speed(Vec2::new(0.05, 0.1) * 0.5),
         MeshMaterial2d(mats.add(ScrollingTexture {
             uv_offset: Vec2::ZERO,
+            background: BackgroundState::default(),
             texture: server.load_with_settings("textures/checkers.png", |s: &mut _| {
                 *s = ImageLoaderSettings {
                     sampler: ImageSampler::Descriptor(ImageSamplerDescriptor {
@@ -105,14 +398,45 @@ fn background(
                 }
             }),
         })),
-    ));
+    )
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Resource)]
+enum LeaderboardTab {
+    #[default]
+    Local,
+    Global,
+}
+
+#[derive(Component)]
+struct TabLabel(LeaderboardTab);
+
+#[derive(Component)]
+struct LeaderboardRow;
+
+fn switch_tab(
+    _: Trigger<Fired<SwitchTab>>,
+    state: Res<State<GameState>>,
+    mut tab: ResMut<LeaderboardTab>,
+) {
+    if *state.get() != GameState::Leaderboard {
+        return;
+    }
+
+    *tab = match *tab {
+        LeaderboardTab::Local => LeaderboardTab::Global,
+        LeaderboardTab::Global => LeaderboardTab::Local,
+    };
 }
 
 fn spawn_leaderboard(
     mut commands: Commands,
     server: Res<AssetServer>,
-    mut data: ResMut<Persistent<PlayerData>>,
+    mut tab: ResMut<LeaderboardTab>,
 ) {
+    // Always open on the local scores so a new entry is visible.
+    *tab = LeaderboardTab::Local;
+
     commands.spawn((
         Leaderboard,
         Text2d::new("LEADERBOARDS"),
@@ -125,17 +449,121 @@ fn spawn_leaderboard(
         Transform::from_xyz(0., crate::RES_HEIGHT / 3., LEADERZ),
     ));
 
-    data.point_record.sort_by(|a, b| b.1.0.cmp(&a.1.0));
-    let largest_text = data
-        .point_record
-        .first()
-        .map(|(level, points)| format!("S{level}   {points}").len())
-        .unwrap_or_default();
-
-    for (i, (level, points)) in data.point_record.iter().enumerate().take(10) {
+    for (label, variant, x) in [
+        ("LOCAL", LeaderboardTab::Local, -100.),
+        ("GLOBAL", LeaderboardTab::Global, 100.),
+    ] {
         commands.spawn((
             Leaderboard,
-            Text2d::new(format!("S{}   {points}", level + 1)),
+            TabLabel(variant),
+            Text2d::new(label),
+            TextFont {
+                font_size: 28.,
+                font: server.load("fonts/cube.ttf"),
+                ..Default::default()
+            },
+            HIGH_RES_LAYER,
+            Transform::from_xyz(x, crate::RES_HEIGHT / 3. - 60., LEADERZ),
+        ));
+    }
+}
+
+/// Tapping a tab label switches to it, tapping anywhere else continues.
+fn tap_leaderboard(
+    mut commands: Commands,
+    mut taps: Taps,
+    labels: Query<(&TabLabel, &GlobalTransform)>,
+    mut tab: ResMut<LeaderboardTab>,
+) {
+    for tap in taps.read() {
+        let label = labels.iter().find(|(_, gt)| {
+            (tap - gt.translation().xy() / crate::RESOLUTION_SCALE)
+                .abs()
+                .cmplt(Vec2::new(70., 20.))
+                .all()
+        });
+
+        match label {
+            Some((label, _)) => *tab = label.0,
+            None => {
+                commands.run_system_cached(remove_entities::<With<Leaderboard>>);
+                commands.send_event(GoTo::new(GameState::StartGame, Transition::slider()));
+            }
+        }
+    }
+}
+
+fn spawn_rows(
+    mut commands: Commands,
+    tab: Res<LeaderboardTab>,
+    data: Res<PlayerSave>,
+    global: Res<GlobalTop>,
+    new: Res<NewEntry>,
+    rows: Query<Entity, With<LeaderboardRow>>,
+    mut labels: Query<(&TabLabel, &mut TextColor)>,
+) {
+    let spawned = !rows.is_empty();
+    if spawned && !tab.is_changed() && !(global.is_changed() && *tab == LeaderboardTab::Global) {
+        return;
+    }
+
+    for entity in rows.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    for (label, mut color) in labels.iter_mut() {
+        color.0 = if label.0 == *tab {
+            POINT_COLOR.into()
+        } else {
+            Color::srgb(0.5, 0.5, 0.5)
+        };
+    }
+
+    let rows = match *tab {
+        LeaderboardTab::Local => data
+            .point_record
+            .iter()
+            .take(LEADERBOARD_SIZE)
+            .map(|entry| {
+                format!(
+                    "{}   S{}   {}   {}",
+                    entry.initials,
+                    entry.level + 1,
+                    entry.points,
+                    format_date(entry.timestamp)
+                )
+            })
+            .collect::<Vec<_>>(),
+        LeaderboardTab::Global => match &*global {
+            GlobalTop::Disabled => vec![String::from("OFFLINE")],
+            GlobalTop::Loading => vec![String::from("LOADING...")],
+            GlobalTop::Failed => vec![String::from("COULD NOT REACH SERVER")],
+            GlobalTop::Loaded(list) if list.entries.is_empty() => {
+                vec![String::from("NO SCORES YET")]
+            }
+            GlobalTop::Loaded(list) => list
+                .entries
+                .iter()
+                .take(LEADERBOARD_SIZE)
+                .map(|entry| {
+                    format!(
+                        "{}   S{}   {}   {}",
+                        entry.initials,
+                        entry.level + 1,
+                        entry.score,
+                        format_date(entry.timestamp)
+                    )
+                })
+                .collect(),
+        },
+    };
+    let largest_text = rows.iter().map(String::len).max().unwrap_or_default();
+
+    for (i, row) in rows.into_iter().enumerate() {
+        let mut entity = commands.spawn((
+            Leaderboard,
+            LeaderboardRow,
+            Text2d::new(row),
             TextFont {
                 font_size: 32.,
                 ..Default::default()
@@ -144,13 +572,29 @@ fn spawn_leaderboard(
             Anchor::CenterLeft,
             Transform::from_xyz(
                 largest_text as f32 * -9.,
-                crate::RES_HEIGHT / 3. - 80. - (i as f32 * 50.),
+                crate::RES_HEIGHT / 3. - 120. - (i as f32 * 50.),
                 LEADERZ,
             ),
         ));
-    }
 
-    if let Err(e) = data.persist() {
-        error!("failed to save player data: {e}");
+        if *tab == LeaderboardTab::Local && new.0 == Some(i) {
+            entity.insert(TextFlash::new(0.25, POINT_COLOR, Color::WHITE));
+        }
     }
 }
+
+/// Formats a unix timestamp as `YYYY-MM-DD`.
+fn format_date(timestamp: u64) -> String {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -0,0 +1,197 @@
+use std::f32::consts::PI;
+
+use avian2d::prelude::LinearVelocity;
+use bevy::prelude::*;
+use bevy_light_2d::light::PointLight2d;
+use bevy_persistent::prelude::*;
+
+use crate::ball::{BallComponents, PaddleRestMult, PlayerBall};
+use crate::collectables::{HexColor, POINT_COLOR};
+use crate::feature::FeatureBonk;
+use crate::music::BeatPulse;
+use crate::settings::Settings;
+use crate::stage::{AdvanceEvent, Stage, last_ball_falling};
+use crate::state::{StateAppExt, insert_resource};
+
+pub struct LightsPlugin;
+
+impl Plugin for LightsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Lighting>()
+            .add_reset(insert_resource(Lighting::default()))
+            .add_systems(Update, (flare_features, light_cabinet).chain());
+    }
+}
+
+/// A light of the cabinet, tinted from its own `color` by how play is going.
+#[derive(Component)]
+#[require(PointLight2d)]
+pub struct CabinetLight {
+    pub color: Color,
+}
+
+/// Flares a feature's [`BeatPulse`] light when the feature is bonked.
+#[derive(Default, Component)]
+pub struct FeatureFlare {
+    level: f32,
+}
+
+#[derive(Clone, Resource)]
+struct Lighting {
+    /// Built up by bonks in quick succession, from 0 to 1.
+    combo: f32,
+    /// How far the cabinet lights are tinted towards [`HOT_COLOR`].
+    heat: f32,
+    /// Brightness of the cabinet lights, lowered while the last ball is in danger.
+    dim: f32,
+    /// The light show playing after a stage is cleared.
+    show: Option<Timer>,
+}
+
+impl Default for Lighting {
+    fn default() -> Self {
+        Self {
+            combo: 0.,
+            heat: 0.,
+            dim: 1.,
+            show: None,
+        }
+    }
+}
+
+const FLARE_PEAK: f32 = 3.;
+/// How much of a flare fades each second.
+const FLARE_DECAY: f32 = 5.;
+/// Flares are halved with reduced flashing.
+const REDUCED_FLARE: f32 = 0.5;
+/// The most the feature flares shine together. Past this every flare is scaled down,
+/// so a crowd of balls can't flood the screen.
+const MAX_FLARE_TOTAL: f32 = 12.;
+
+const COMBO_PER_BONK: f32 = 0.04;
+const COMBO_DECAY: f32 = 0.3;
+/// Paddle rest, in seconds, that tints the lights all the way.
+const PADDLE_HOT_SECS: f32 = 4.;
+const HOT_COLOR: HexColor = POINT_COLOR;
+/// How quickly the tint and dimming catch up, per second.
+const HEAT_RATE: f32 = 3.;
+const DIM_RATE: f32 = 4.;
+
+const IDLE_INTENSITY: f32 = 4.9;
+/// The lights slowly breathe around their idle intensity.
+const BREATH: f32 = 0.1;
+const DANGER_DIM: f32 = 0.35;
+const MAX_CABINET_INTENSITY: f32 = 8.;
+
+const SHOW_SECS: f32 = 2.5;
+/// Turns of the hue wheel each second.
+const SHOW_SPIN: f32 = 0.8;
+const SHOW_STROBE_HZ: f32 = 6.;
+const SHOW_BOOST: f32 = 0.6;
+
+fn flare_features(
+    time: Res<Time>,
+    settings: Res<Persistent<Settings>>,
+    mut lighting: ResMut<Lighting>,
+    mut bonks: EventReader<FeatureBonk>,
+    features: Query<&Children>,
+    mut flares: Query<(&mut FeatureFlare, &mut BeatPulse)>,
+) {
+    for bonk in bonks.read() {
+        lighting.combo = (lighting.combo + COMBO_PER_BONK).min(1.);
+        let Ok(children) = features.get(bonk.feature) else {
+            continue;
+        };
+        let mut flares = flares.iter_many_mut(children);
+        while let Some((mut flare, _)) = flares.fetch_next() {
+            flare.level = 1.;
+        }
+    }
+    lighting.combo = (lighting.combo - COMBO_DECAY * time.delta_secs()).max(0.);
+
+    let peak = if settings.reduced_flashing {
+        FLARE_PEAK * REDUCED_FLARE
+    } else {
+        FLARE_PEAK
+    };
+    let mut total = 0.;
+    for (mut flare, _) in flares.iter_mut() {
+        flare.level = (flare.level - FLARE_DECAY * time.delta_secs()).max(0.);
+        total += peak * flare.level * flare.level;
+    }
+
+    let cap = if total > MAX_FLARE_TOTAL {
+        MAX_FLARE_TOTAL / total
+    } else {
+        1.
+    };
+    for (flare, mut pulse) in flares.iter_mut() {
+        pulse.flare = peak * flare.level * flare.level * cap;
+    }
+}
+
+/// Tints the cabinet lights with the combo and paddle multiplier, dims them while the last
+/// ball falls and plays a light show when a stage is cleared.
+fn light_cabinet(
+    time: Res<Time>,
+    settings: Res<Persistent<Settings>>,
+    mut lighting: ResMut<Lighting>,
+    mut advances: EventReader<AdvanceEvent>,
+    paddle: Option<Single<&PaddleRestMult, With<PlayerBall>>>,
+    stage: Option<Single<&Stage>>,
+    balls: Query<(&Transform, &LinearVelocity), With<BallComponents>>,
+    mut lights: Query<(&CabinetLight, &mut PointLight2d)>,
+) {
+    if advances.read().last().is_some() {
+        lighting.show = Some(Timer::from_seconds(SHOW_SECS, TimerMode::Once));
+    }
+
+    let delta = time.delta_secs();
+    let paddle = paddle.map_or(0., |paddle| paddle.0 / PADDLE_HOT_SECS);
+    let heat = lighting.combo.max(paddle).min(1.);
+    lighting.heat += (heat - lighting.heat) * (HEAT_RATE * delta).min(1.);
+
+    let danger = stage.is_some_and(|stage| {
+        balls
+            .single()
+            .is_ok_and(|(transform, velocity)| last_ball_falling(&stage, transform, velocity))
+    });
+    let dim = if danger { DANGER_DIM } else { 1. };
+    lighting.dim += (dim - lighting.dim) * (DIM_RATE * delta).min(1.);
+
+    // How strongly the show plays, fading out towards its end.
+    let mut show = 0.;
+    let mut show_secs = 0.;
+    if let Some(timer) = lighting.show.as_mut() {
+        timer.tick(time.delta());
+        show = 1. - timer.fraction().powi(2);
+        show_secs = timer.elapsed_secs();
+        if timer.finished() {
+            lighting.show = None;
+        }
+    }
+
+    let breath = BREATH * (time.elapsed_secs() * PI).sin();
+    let count = lights.iter().count().max(1) as f32;
+    for (index, (light, mut point)) in lights.iter_mut().enumerate() {
+        let mut color = light.color.mix(&HOT_COLOR.into(), lighting.heat);
+        let mut intensity = (IDLE_INTENSITY + breath) * lighting.dim;
+
+        if show > 0. {
+            let hue = (show_secs * SHOW_SPIN + index as f32 / count).fract() * 360.;
+            color = color.mix(&Color::hsl(hue, 0.8, 0.6), show);
+
+            // A strobe, or a single swell with reduced flashing.
+            let strobe = if settings.reduced_flashing || (show_secs * SHOW_STROBE_HZ).fract() < 0.5
+            {
+                1.
+            } else {
+                0.
+            };
+            intensity += IDLE_INTENSITY * SHOW_BOOST * strobe * show;
+        }
+
+        point.color = color;
+        point.intensity = intensity.min(MAX_CABINET_INTENSITY);
+    }
+}
@@ -15,29 +15,46 @@ use bevy::winit::WinitWindows;
 use bevy_optix::pixel_perfect::CanvasDimensions;
 use winit::window::Icon;
 
+mod accessibility;
+mod achievements;
 mod animation;
+mod audio;
 mod ball;
 mod big;
 mod cabinet;
 mod collectables;
+mod controls;
 mod cursor;
 mod feature;
+mod focus;
+mod hitstop;
+mod hud;
 mod input;
 mod leaderboard;
+mod lights;
 mod loading;
 mod menu;
 mod music;
+mod online;
 mod paddle;
 mod particles;
+mod pause;
+mod popup;
+mod rng;
 mod sampler;
 mod sandbox;
+mod save;
 mod selection;
+mod settings;
+mod shake;
 mod slugger;
 mod sprites;
 mod stage;
 mod state;
 mod text;
 mod tooltips;
+mod touch;
+mod transition;
 mod tween;
 
 pub const WIDTH: f32 = 750.;
@@ -52,9 +69,6 @@ pub const GRAVITY: f32 = 400.;
 fn main() {
     let mut app = App::new();
 
-    #[cfg(debug_assertions)]
-    app.add_systems(Update, close_on_escape);
-
     app.add_plugins((
         DefaultPlugins
             .set(WindowPlugin {
@@ -115,6 +129,25 @@ fn main() {
         music::MusicPlugin,
         animation::AnimationPlugin,
         sprites::SpritePlugin,
+        rng::RngPlugin,
+        save::SavePlugin,
+        online::OnlinePlugin,
+        achievements::AchievementPlugin,
+        controls::ControlsPlugin,
+        touch::TouchPlugin,
+        focus::FocusPlugin,
+    ))
+    .add_plugins((
+        settings::SettingsPlugin,
+        pause::PausePlugin,
+        audio::AudioPlugin,
+        shake::ShakePlugin,
+        hitstop::HitStopPlugin,
+        popup::PopupPlugin,
+        hud::HudPlugin,
+        accessibility::AccessibilityPlugin,
+        transition::TransitionPlugin,
+        lights::LightsPlugin,
     ))
     .add_plugins((avian2d::debug_render::PhysicsDebugPlugin::new(Avian),))
     .init_schedule(Avian)
@@ -179,10 +212,3 @@ fn set_window_icon(
 
     Ok(())
 }
-
-#[cfg(debug_assertions)]
-fn close_on_escape(input: Res<ButtonInput<KeyCode>>, mut writer: EventWriter<AppExit>) {
-    if input.just_pressed(KeyCode::Escape) {
-        writer.write(AppExit::Success);
-    }
-}
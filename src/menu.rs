@@ -3,7 +3,8 @@ use std::time::Duration;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use bevy_light_2d::light::PointLight2d;
-use bevy_optix::pixel_perfect::OuterCamera;
+use bevy_optix::pixel_perfect::{HIGH_RES_LAYER, OuterCamera};
+use bevy_persistent::prelude::*;
 use bevy_tween::combinator::{sequence, tween};
 use bevy_tween::interpolate::sprite_color;
 use bevy_tween::prelude::{AnimationBuilderExt, EaseKind, Interpolator, Repeat};
@@ -11,8 +12,13 @@ use bevy_tween::tween::IntoTarget;
 
 use crate::animation::{AnimationAppExt, AnimationSprite};
 use crate::cabinet::{point_light_color, point_light_intensity};
-use crate::collectables::HexColor;
+use crate::collectables::{HexColor, POINT_COLOR};
+use crate::focus::{Activate, Focusable, Focused};
+use crate::save::{ResumeRun, RunSave};
+use crate::settings::Settings;
 use crate::state::{self, GameState};
+use crate::touch::Taps;
+use crate::transition::{GoTo, MENU_FADE_SECS, Transition};
 
 pub struct MenuPlugin;
 
@@ -27,7 +33,16 @@ impl Plugin for MenuPlugin {
             TextureAtlasLayout::from_grid(UVec2::new(60, 34), 17, 3, None, None),
         )
         .add_systems(OnEnter(GameState::Menu), setup_menu)
-        .add_systems(Update, await_enter.in_set(state::Menu))
+        .add_systems(
+            Update,
+            (
+                await_enter,
+                await_continue,
+                await_achievements,
+                await_controls,
+            )
+                .in_set(state::Menu),
+        )
         .add_systems(OnExit(GameState::Menu), cleanup_menu);
     }
 }
@@ -35,7 +50,12 @@ impl Plugin for MenuPlugin {
 #[derive(Component)]
 struct Menu;
 
-fn setup_menu(mut commands: Commands, server: Res<AssetServer>) {
+fn setup_menu(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    save: Res<Persistent<RunSave>>,
+    settings: Res<Persistent<Settings>>,
+) {
     let scale = Vec3::new(crate::WIDTH / 200., crate::HEIGHT / 200., 0.);
 
     commands.spawn((
@@ -71,31 +91,34 @@ fn setup_menu(mut commands: Commands, server: Res<AssetServer>) {
         ))
         .id();
 
-    commands
-        .animation()
-        .repeat(Repeat::Infinitely)
-        .insert(sequence((
-            tween(
-                Duration::from_secs_f32(0.1),
-                EaseKind::SineInOut,
-                sign.into_target().with(point_light_intensity(0.3, 0.5)),
-            ),
-            tween(
-                Duration::from_secs_f32(0.15),
-                EaseKind::SineInOut,
-                sign.into_target().with(point_light_intensity(0.5, 0.4)),
-            ),
-            tween(
-                Duration::from_secs_f32(0.08),
-                EaseKind::SineInOut,
-                sign.into_target().with(point_light_intensity(0.4, 0.6)),
-            ),
-            tween(
-                Duration::from_secs_f32(0.12),
-                EaseKind::SineInOut,
-                sign.into_target().with(point_light_intensity(0.6, 0.3)),
-            ),
-        )));
+    // The sign flickers, unless flashing is reduced.
+    if !settings.reduced_flashing {
+        commands
+            .animation()
+            .repeat(Repeat::Infinitely)
+            .insert(sequence((
+                tween(
+                    Duration::from_secs_f32(0.1),
+                    EaseKind::SineInOut,
+                    sign.into_target().with(point_light_intensity(0.3, 0.5)),
+                ),
+                tween(
+                    Duration::from_secs_f32(0.15),
+                    EaseKind::SineInOut,
+                    sign.into_target().with(point_light_intensity(0.5, 0.4)),
+                ),
+                tween(
+                    Duration::from_secs_f32(0.08),
+                    EaseKind::SineInOut,
+                    sign.into_target().with(point_light_intensity(0.4, 0.6)),
+                ),
+                tween(
+                    Duration::from_secs_f32(0.12),
+                    EaseKind::SineInOut,
+                    sign.into_target().with(point_light_intensity(0.6, 0.3)),
+                ),
+            )));
+    }
 
     //commands.spawn((
     //    Menu,
@@ -112,7 +135,7 @@ fn setup_menu(mut commands: Commands, server: Res<AssetServer>) {
             Sprite::from_color(Color::WHITE, Vec2::new(crate::WIDTH, crate::HEIGHT)),
         ))
         .id();
-    insert_light_tweens(&mut commands, light, sprite_color);
+    insert_light_tweens(&mut commands, light, sprite_color, &settings);
 
     let point_light = commands
         .spawn((
@@ -125,14 +148,76 @@ fn setup_menu(mut commands: Commands, server: Res<AssetServer>) {
             },
         ))
         .id();
-    insert_light_tweens(&mut commands, point_light, point_light_color);
+    insert_light_tweens(&mut commands, point_light, point_light_color, &settings);
+
+    commands.spawn((
+        Menu,
+        PlayButton,
+        Focusable::at(PLAY_POSITION),
+        Transform::from_translation(PLAY_POSITION.extend(0.)),
+    ));
+
+    if save.snapshot().is_some() {
+        commands.spawn((
+            Menu,
+            ContinueText,
+            Focusable::at(CONTINUE_POSITION),
+            HIGH_RES_LAYER,
+            Text2d::new("CONTINUE"),
+            TextFont {
+                font_size: 32.,
+                font: server.load("fonts/saiba.ttf"),
+                ..Default::default()
+            },
+            Transform::from_translation(CONTINUE_POSITION.extend(10.)),
+        ));
+    }
+
+    commands.spawn((
+        Menu,
+        AchievementsText,
+        Focusable::at(ACHIEVEMENTS_POSITION),
+        HIGH_RES_LAYER,
+        Text2d::new("ACHIEVEMENTS"),
+        TextFont {
+            font_size: 24.,
+            font: server.load("fonts/saiba.ttf"),
+            ..Default::default()
+        },
+        Transform::from_translation(ACHIEVEMENTS_POSITION.extend(10.)),
+    ));
+
+    commands.spawn((
+        Menu,
+        ControlsText,
+        Focusable::at(CONTROLS_POSITION),
+        HIGH_RES_LAYER,
+        Text2d::new("CONTROLS"),
+        TextFont {
+            font_size: 24.,
+            font: server.load("fonts/saiba.ttf"),
+            ..Default::default()
+        },
+        Transform::from_translation(CONTROLS_POSITION.extend(10.)),
+    ));
 }
 
+/// Length of each step of the menu light cycle with reduced flashing.
+const REDUCED_FLASH_STEP_SECS: f32 = 1.5;
+
 fn insert_light_tweens<I: Interpolator>(
     commands: &mut Commands,
     entity: Entity,
     the_tween: impl Fn(Color, Color) -> I,
+    settings: &Settings,
 ) {
+    // Reduced flashing drifts between the colors instead of pulsing through them.
+    let step = Duration::from_secs_f32(if settings.reduced_flashing {
+        REDUCED_FLASH_STEP_SECS
+    } else {
+        0.3
+    });
+
     let red: Color = HexColor(0xb4202a).into();
     let orange: Color = HexColor(0xfa6a0a).into();
     let purple: Color = HexColor(0xbc4a9b).into();
@@ -153,32 +238,32 @@ fn insert_light_tweens<I: Interpolator>(
         .repeat(Repeat::Infinitely)
         .insert(sequence((
             tween(
-                Duration::from_secs_f32(0.3),
+                step,
                 EaseKind::SineInOut,
                 entity.into_target().with(the_tween(red_low, red)),
             ),
             tween(
-                Duration::from_secs_f32(0.3),
+                step,
                 EaseKind::SineInOut,
                 entity.into_target().with(the_tween(red, orange_low)),
             ),
             tween(
-                Duration::from_secs_f32(0.3),
+                step,
                 EaseKind::SineInOut,
                 entity.into_target().with(the_tween(orange_low, orange)),
             ),
             tween(
-                Duration::from_secs_f32(0.3),
+                step,
                 EaseKind::SineInOut,
                 entity.into_target().with(the_tween(orange, purple_low)),
             ),
             tween(
-                Duration::from_secs_f32(0.3),
+                step,
                 EaseKind::SineInOut,
                 entity.into_target().with(the_tween(purple_low, purple)),
             ),
             tween(
-                Duration::from_secs_f32(0.3),
+                step,
                 EaseKind::SineInOut,
                 entity.into_target().with(the_tween(purple, red_low)),
             ),
@@ -188,6 +273,11 @@ fn insert_light_tweens<I: Interpolator>(
 #[derive(Component)]
 struct EnterSprite;
 
+#[derive(Component)]
+struct PlayButton;
+
+const PLAY_POSITION: Vec2 = Vec2::new(12., -100.);
+
 fn await_enter(
     mut commands: Commands,
     server: Res<AssetServer>,
@@ -195,40 +285,170 @@ fn await_enter(
     window: Single<&Window, With<PrimaryWindow>>,
     camera: Single<(&Camera, &GlobalTransform), With<OuterCamera>>,
 
+    play: Single<(Entity, Has<Focused>), With<PlayButton>>,
     enter: Option<Single<Entity, With<EnterSprite>>>,
+    mut taps: Taps,
+    mut activations: EventReader<Activate>,
 ) {
+    let (play, focused) = play.into_inner();
     let (camera, gt) = camera.into_inner();
-    let Some(world_position) = window
+    let hovered = window
         .cursor_position()
         .and_then(|cursor| camera.viewport_to_world(gt, cursor).ok())
         .map(|ray| ray.origin.truncate() / crate::RESOLUTION_SCALE)
-    else {
+        .is_some_and(over_play);
+
+    let tapped = taps.read().fold(false, |hit, tap| hit || over_play(tap));
+    if tapped
+        || (hovered && input.just_pressed(MouseButton::Left))
+        || activated(&mut activations, play)
+    {
+        commands.send_event(GoTo::new(GameState::StartGame, Transition::slider()));
         return;
-    };
+    }
 
-    let target = Vec2::new(12., -100.);
-    if world_position.distance_squared(target) > 8_000. {
-        if let Some(entity) = enter {
+    match (hovered || focused, enter) {
+        (true, None) => {
+            let scale = Vec3::new(crate::WIDTH / 200., crate::HEIGHT / 200., 0.);
+            commands.spawn((
+                Menu,
+                EnterSprite,
+                Transform::from_scale(scale).with_translation(Vec3::Z * 10.),
+                Sprite::from_image(server.load("textures/menu_enter.png")),
+            ));
+        }
+        (false, Some(entity)) => {
             commands.entity(*entity).despawn();
         }
-        return;
+        _ => {}
     }
+}
 
-    let scale = Vec3::new(crate::WIDTH / 200., crate::HEIGHT / 200., 0.);
-    if enter.is_none() {
-        commands.spawn((
-            Menu,
-            EnterSprite,
-            Transform::from_scale(scale).with_translation(Vec3::Z * 10.),
-            Sprite::from_image(server.load("textures/menu_enter.png")),
+fn over_play(point: Vec2) -> bool {
+    point.distance_squared(PLAY_POSITION) <= 8_000.
+}
+
+#[derive(Component)]
+struct ContinueText;
+
+const CONTINUE_POSITION: Vec2 = Vec2::new(12., -260.);
+
+fn await_continue(
+    mut commands: Commands,
+    input: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<OuterCamera>>,
+    text: Single<(Entity, &mut TextColor, Has<Focused>), With<ContinueText>>,
+    save: Res<Persistent<RunSave>>,
+    mut taps: Taps,
+    mut activations: EventReader<Activate>,
+) {
+    let (entity, color, focused) = text.into_inner();
+    let hovered = hover_text(&window, &camera, color, focused, CONTINUE_POSITION);
+    if (hovered && input.just_pressed(MouseButton::Left))
+        || tapped(&mut taps, CONTINUE_POSITION)
+        || activated(&mut activations, entity)
+    {
+        if let Some(snapshot) = save.snapshot() {
+            commands.insert_resource(ResumeRun(snapshot.clone()));
+            commands.send_event(GoTo::new(GameState::StartGame, Transition::slider()));
+        }
+    }
+}
+
+#[derive(Component)]
+struct AchievementsText;
+
+const ACHIEVEMENTS_POSITION: Vec2 = Vec2::new(12., -300.);
+
+fn await_achievements(
+    mut commands: Commands,
+    input: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<OuterCamera>>,
+    text: Single<(Entity, &mut TextColor, Has<Focused>), With<AchievementsText>>,
+    mut taps: Taps,
+    mut activations: EventReader<Activate>,
+) {
+    let (entity, color, focused) = text.into_inner();
+    let hovered = hover_text(&window, &camera, color, focused, ACHIEVEMENTS_POSITION);
+    if (hovered && input.just_pressed(MouseButton::Left))
+        || tapped(&mut taps, ACHIEVEMENTS_POSITION)
+        || activated(&mut activations, entity)
+    {
+        commands.send_event(GoTo::new(
+            GameState::Achievements,
+            Transition::fade().with_duration(MENU_FADE_SECS),
         ));
     }
+}
 
-    if input.just_pressed(MouseButton::Left) {
-        commands.set_state(GameState::ToGame);
+#[derive(Component)]
+struct ControlsText;
+
+const CONTROLS_POSITION: Vec2 = Vec2::new(12., -335.);
+
+fn await_controls(
+    mut commands: Commands,
+    input: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<OuterCamera>>,
+    text: Single<(Entity, &mut TextColor, Has<Focused>), With<ControlsText>>,
+    mut taps: Taps,
+    mut activations: EventReader<Activate>,
+) {
+    let (entity, color, focused) = text.into_inner();
+    let hovered = hover_text(&window, &camera, color, focused, CONTROLS_POSITION);
+    if (hovered && input.just_pressed(MouseButton::Left))
+        || tapped(&mut taps, CONTROLS_POSITION)
+        || activated(&mut activations, entity)
+    {
+        commands.send_event(GoTo::new(
+            GameState::Controls,
+            Transition::fade().with_duration(MENU_FADE_SECS),
+        ));
     }
 }
 
+/// Highlights `text` while the cursor is near `position` or it has the focus, and returns
+/// whether the cursor is.
+fn hover_text(
+    window: &Window,
+    camera: &(&Camera, &GlobalTransform),
+    mut text: Mut<TextColor>,
+    focused: bool,
+    position: Vec2,
+) -> bool {
+    let (camera, gt) = *camera;
+    let hovered = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world(gt, cursor).ok())
+        .map(|ray| ray.origin.truncate() / crate::RESOLUTION_SCALE)
+        .is_some_and(|world_position| over_text(world_position, position));
+
+    text.0 = if hovered || focused {
+        POINT_COLOR.into()
+    } else {
+        Color::WHITE
+    };
+    hovered
+}
+
+fn tapped(taps: &mut Taps, position: Vec2) -> bool {
+    taps.read()
+        .fold(false, |hit, tap| hit || over_text(tap, position))
+}
+
+fn activated(activations: &mut EventReader<Activate>, entity: Entity) -> bool {
+    activations
+        .read()
+        .fold(false, |hit, activate| hit || activate.0 == entity)
+}
+
+fn over_text(point: Vec2, position: Vec2) -> bool {
+    (point - position).abs().cmplt(Vec2::new(90., 16.)).all()
+}
+
 fn cleanup_menu(mut commands: Commands, menu: Query<Entity, With<Menu>>) {
     for entity in menu.iter() {
         commands.entity(entity).despawn();
@@ -1,12 +1,21 @@
 use bevy::prelude::*;
+use bevy_light_2d::light::PointLight2d;
+use bevy_persistent::prelude::*;
 use bevy_seedling::{pool::Sampler, prelude::*};
 
+use super::playlist::CurrentTrack;
+use crate::settings::Settings;
+
 /// A measure of where the music is in its beat pattern,
 /// normalized for each piece of music.
 #[derive(Component)]
 pub struct MusicBeats {
     position: f32,
     bpm: f32,
+    /// Seconds from the start of the track to its first beat.
+    offset: f32,
+    beats_per_bar: u32,
+    last_beat: Option<u32>,
 }
 
 impl MusicBeats {
@@ -18,25 +27,172 @@ impl MusicBeats {
         Self {
             position: 0f32,
             bpm,
+            offset: 0.,
+            beats_per_bar: 4,
+            last_beat: None,
         }
     }
+
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_beats_per_bar(mut self, beats_per_bar: u32) -> Self {
+        self.beats_per_bar = beats_per_bar;
+        self
+    }
+
+    /// Seconds between the playhead and the nearest beat.
+    pub fn distance(&self) -> f32 {
+        (self.position - self.position.round()).abs() * 60. / self.bpm
+    }
+
+    /// Whether the playhead is within `tolerance` seconds of a beat.
+    pub fn on_beat(&self, tolerance: f32) -> bool {
+        self.position >= -0.5 && self.distance() <= tolerance
+    }
+}
+
+/// Triggered globally each time the [`CurrentTrack`] crosses a beat.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct OnBeat {
+    /// The beat within its bar, 0 being the downbeat.
+    pub beat: u32,
+    pub bar: u32,
 }
 
 pub fn update_beats(
-    mut beats: Query<(&mut MusicBeats, &Sampler)>,
+    mut commands: Commands,
+    mut beats: Query<(&mut MusicBeats, &Sampler, Has<CurrentTrack>)>,
     mut context: ResMut<AudioContext>,
 ) {
     let sample_rate = context
         .with(|c| c.stream_info().map(|i| i.sample_rate.get()))
         .unwrap_or(441000);
 
-    for (mut beats, sampler) in beats.iter_mut() {
+    for (mut beats, sampler, current) in beats.iter_mut() {
         let beats_per_second = beats.bpm / 60.0;
         let Some(frames) = sampler.try_playhead_frames() else {
             continue;
         };
         let seconds = frames as f32 / sample_rate as f32;
 
-        beats.position = seconds * beats_per_second;
+        beats.position = (seconds - beats.offset) * beats_per_second;
+
+        if beats.position < 0. {
+            beats.last_beat = None;
+            continue;
+        }
+
+        // The playhead jumps back when the track loops, which starts the count over.
+        let beat = beats.position as u32;
+        if beats.last_beat.is_none_or(|last| beat != last) {
+            beats.last_beat = Some(beat);
+            if current {
+                commands.trigger(OnBeat {
+                    beat: beat % beats.beats_per_bar,
+                    bar: beat / beats.beats_per_bar,
+                });
+            }
+        }
+    }
+}
+
+/// A light that flashes on each beat and fades out before the next.
+///
+/// With reduced flashing it glows steadily instead.
+///
+/// The pulse owns the light's intensity, so it should be a light of its own
+/// rather than one that is already tweened. Other effects add to it through `flare`.
+#[derive(Component)]
+#[require(PointLight2d)]
+pub struct BeatPulse {
+    pub peak: f32,
+    /// Intensity added on top of the pulse.
+    pub flare: f32,
+    level: f32,
+}
+
+impl BeatPulse {
+    pub fn new(peak: f32) -> Self {
+        Self {
+            peak,
+            flare: 0.,
+            level: 0.,
+        }
+    }
+}
+
+/// Downbeats flash at full strength, the other beats a little softer.
+const OFFBEAT_LEVEL: f32 = 0.6;
+
+/// How much of a pulse fades each second.
+const PULSE_DECAY: f32 = 4.;
+
+/// The level pulses hold with reduced flashing.
+const STEADY_LEVEL: f32 = 0.5;
+/// How quickly pulses ease to and from the steady level, per second.
+const STEADY_RATE: f32 = 2.;
+
+pub fn pulse_on_beat(
+    trigger: Trigger<OnBeat>,
+    settings: Res<Persistent<Settings>>,
+    mut pulses: Query<&mut BeatPulse>,
+) {
+    if settings.reduced_flashing {
+        return;
+    }
+
+    let level = if trigger.beat == 0 { 1. } else { OFFBEAT_LEVEL };
+    for mut pulse in pulses.iter_mut() {
+        pulse.level = pulse.level.max(level);
+    }
+}
+
+pub fn fade_pulses(
+    time: Res<Time>,
+    settings: Res<Persistent<Settings>>,
+    mut pulses: Query<(&mut BeatPulse, &mut PointLight2d)>,
+) {
+    let delta = time.delta_secs();
+    for (mut pulse, mut light) in pulses.iter_mut() {
+        pulse.level = if settings.reduced_flashing {
+            pulse.level + (STEADY_LEVEL - pulse.level) * (STEADY_RATE * delta).min(1.)
+        } else {
+            (pulse.level - PULSE_DECAY * delta).max(0.)
+        };
+        light.intensity = pulse.peak * pulse.level * pulse.level + pulse.flare;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(position: f32) -> MusicBeats {
+        MusicBeats {
+            position,
+            ..MusicBeats::new(120.)
+        }
+    }
+
+    #[test]
+    fn distance_to_nearest_beat() {
+        // Half a second a beat at 120 bpm.
+        assert_eq!(at(4.).distance(), 0.);
+        assert!((at(4.1).distance() - 0.05).abs() < 1e-5);
+        assert!((at(3.9).distance() - 0.05).abs() < 1e-5);
+        assert!((at(4.5).distance() - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn on_beat_within_tolerance() {
+        assert!(at(8.).on_beat(0.));
+        assert!(at(8.1).on_beat(0.1));
+        assert!(!at(8.3).on_beat(0.1));
+        // Before the first beat only its lead in counts.
+        assert!(at(-0.1).on_beat(0.1));
+        assert!(!at(-0.9).on_beat(0.1));
     }
 }
@@ -0,0 +1,212 @@
+use std::time::Duration;
+
+use bevy::math::FloatExt;
+use bevy::prelude::*;
+use bevy_seedling::prelude::*;
+use bevy_tween::{
+    combinator::{parallel, tween},
+    prelude::{AnimationBuilderExt, EaseKind},
+    tween::IntoTarget,
+};
+
+use super::playlist::CurrentTrack;
+use super::{OPEN_FREQ, interpolators};
+use crate::ball::BallComponents;
+use crate::big::BigPoints;
+use crate::collectables::{PointEvent, Points};
+use crate::stage::Stage;
+
+/// Filters the music down while play is calm, independently of the state tweens.
+#[derive(Component)]
+pub(super) struct IntensityLowPass;
+
+#[derive(Component)]
+pub(super) struct IntensityVolume;
+
+/// Follows how hectic play is and steers the music to match.
+#[derive(Resource)]
+pub(super) struct MusicDirector {
+    /// Smoothed from 0, calm, to 1, a full chain reaction.
+    intensity: f32,
+    /// The intensity the music was last steered to.
+    applied: f32,
+    /// Share of the stage target scored per second, smoothed.
+    points_rate: f32,
+    /// Leaves the music to the start tweens for a moment after entering play.
+    settle: Timer,
+    steer: Timer,
+    tween: Option<Entity>,
+}
+
+impl Default for MusicDirector {
+    fn default() -> Self {
+        Self {
+            intensity: 0.,
+            applied: 0.,
+            points_rate: 0.,
+            settle: Timer::from_seconds(SETTLE_SECS, TimerMode::Once),
+            steer: Timer::from_seconds(STEER_SECS, TimerMode::Repeating),
+            tween: None,
+        }
+    }
+}
+
+const CALM_FREQ: f32 = 6_000.;
+const CALM_VOLUME: f32 = -3.;
+const PEAK_SPEED: f32 = 1.05;
+
+const SETTLE_SECS: f32 = 2.5;
+const STEER_SECS: f32 = 0.25;
+/// Smallest change in intensity worth steering the music for.
+const STEER_THRESHOLD: f32 = 0.04;
+
+/// Intensity per second while play heats up and cools down. Rising fast and falling
+/// slowly keeps a chain reaction sounding like one event instead of many.
+const ATTACK: f32 = 3.;
+const RELEASE: f32 = 0.4;
+
+/// Number of live balls that counts as a full chain reaction, as a power of 2.
+const PEAK_BALLS_LOG2: f32 = 8.;
+/// Share of the stage target scored per second that counts as full intensity.
+const PEAK_POINTS_RATE: f32 = 0.1;
+const POINTS_RATE_SMOOTHING: f32 = 2.;
+
+pub(super) fn reset_director(mut commands: Commands, mut director: ResMut<MusicDirector>) {
+    if let Some(tween) = director.tween.take() {
+        commands.entity(tween).try_despawn();
+    }
+    *director = MusicDirector::default();
+}
+
+pub(super) fn measure_intensity(
+    time: Res<Time>,
+    mut director: ResMut<MusicDirector>,
+    mut reader: EventReader<PointEvent>,
+    balls: Query<(), With<BallComponents>>,
+    points: Res<Points>,
+    stage: Option<Single<&Stage>>,
+) {
+    let dt = time.delta_secs();
+    if dt <= 0. {
+        return;
+    }
+
+    let Some(stage) = stage else {
+        reader.clear();
+        return;
+    };
+    let target = &stage.points;
+
+    let mut scored = BigPoints::default();
+    for event in reader.read() {
+        scored.0 += event.points.0.clone();
+    }
+    let rate = scored.share(target) / dt;
+    director.points_rate += (rate - director.points_rate) * (POINTS_RATE_SMOOTHING * dt).min(1.);
+
+    let balls = ((balls.iter().count() as f32).max(1.).log2() / PEAK_BALLS_LOG2).min(1.);
+    let rate = (director.points_rate / PEAK_POINTS_RATE).min(1.);
+    let closeness = points.get().share(target).min(1.);
+
+    let goal = (balls.max(rate) * 0.8 + closeness * 0.2).clamp(0., 1.);
+    let step = if goal > director.intensity {
+        ATTACK
+    } else {
+        RELEASE
+    } * dt;
+    director.intensity += (goal - director.intensity).clamp(-step, step);
+}
+
+pub(super) fn steer_music(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut director: ResMut<MusicDirector>,
+    low_pass: Single<(Entity, &LowPassNode), With<IntensityLowPass>>,
+    volume: Single<(Entity, &VolumeNode), With<IntensityVolume>>,
+    music: Single<(Entity, &PlaybackSettings), With<CurrentTrack>>,
+) {
+    if !director.settle.tick(time.delta()).finished()
+        || !director.steer.tick(time.delta()).just_finished()
+        || (director.intensity - director.applied).abs() < STEER_THRESHOLD
+    {
+        return;
+    }
+
+    let intensity = director.intensity;
+    director.applied = intensity;
+    if let Some(tween) = director.tween.take() {
+        commands.entity(tween).try_despawn();
+    }
+
+    let (low_pass, node) = low_pass.into_inner();
+    let mut low_pass = low_pass.into_target().state(node.frequency);
+    let (volume, node) = volume.into_inner();
+    let mut volume = volume.into_target().state(node.volume.decibels());
+    let (music, settings) = music.into_inner();
+    let mut music = music.into_target().state(settings.speed as f32);
+
+    let duration = Duration::from_secs_f32(STEER_SECS * 2.);
+    let ease = EaseKind::QuadraticInOut;
+    let tween = commands
+        .animation()
+        .insert(parallel((
+            tween(
+                duration,
+                ease,
+                low_pass.with(interpolators::low_pass_to(frequency(intensity))),
+            ),
+            tween(
+                duration,
+                ease,
+                volume.with(interpolators::volume_to(CALM_VOLUME.lerp(0., intensity))),
+            ),
+            tween(
+                duration,
+                ease,
+                music.with(interpolators::sample_speed_to(
+                    1f32.lerp(PEAK_SPEED, intensity),
+                )),
+            ),
+        )))
+        .id();
+    director.tween = Some(tween);
+}
+
+/// Opens the filter evenly across octaves rather than hertz.
+fn frequency(intensity: f32) -> f32 {
+    CALM_FREQ * (OPEN_FREQ / CALM_FREQ).powf(intensity)
+}
+
+/// Leaves the music neutral outside of play, for the menus and the state tweens.
+pub(super) fn release_music(
+    mut commands: Commands,
+    low_pass: Single<(Entity, &LowPassNode), With<IntensityLowPass>>,
+    volume: Single<(Entity, &VolumeNode), With<IntensityVolume>>,
+    music: Single<(Entity, &PlaybackSettings), With<CurrentTrack>>,
+) {
+    let (low_pass, node) = low_pass.into_inner();
+    let mut low_pass = low_pass.into_target().state(node.frequency);
+    let (volume, node) = volume.into_inner();
+    let mut volume = volume.into_target().state(node.volume.decibels());
+    let (music, settings) = music.into_inner();
+    let mut music = music.into_target().state(settings.speed as f32);
+
+    let duration = Duration::from_secs(1);
+    commands.animation().insert(parallel((
+        tween(
+            duration,
+            EaseKind::QuadraticOut,
+            low_pass.with(interpolators::low_pass_to(OPEN_FREQ)),
+        ),
+        tween(
+            duration,
+            EaseKind::QuadraticOut,
+            volume.with(interpolators::volume_to(0.)),
+        ),
+        tween(
+            duration,
+            EaseKind::QuadraticOut,
+            music.with(interpolators::sample_speed_to(1.)),
+        ),
+    )));
+}
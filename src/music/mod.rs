@@ -11,12 +11,19 @@ use bevy_tween::{
 };
 
 mod beats;
+mod director;
 mod interpolators;
+mod playlist;
 
-pub use beats::MusicBeats;
+pub use beats::{BeatPulse, MusicBeats, OnBeat};
 use interpolators::{InterpolateLowPass, InterpolateSampleSpeed, InterpolateVolume};
+pub use playlist::CurrentTrack;
+use playlist::Playlist;
 
-use crate::state::GameState;
+use crate::audio::{Bus, MusicCuePool, MusicFadePool, MusicPool};
+use crate::loading::RonLoader;
+use crate::pause::PauseState;
+use crate::state::{GameState, Playing};
 
 pub struct MusicPlugin;
 
@@ -25,8 +32,33 @@ impl Plugin for MusicPlugin {
         app.register_type::<InterpolateLowPass>()
             .register_type::<InterpolateSampleSpeed>()
             .register_type::<InterpolateVolume>()
-            .add_systems(Startup, spawn_music)
+            .init_asset::<Playlist>()
+            .init_asset_loader::<RonLoader<Playlist>>()
+            .add_systems(Startup, (spawn_music, playlist::load_playlist))
             .add_systems(PreUpdate, beats::update_beats)
+            .init_resource::<director::MusicDirector>()
+            .add_systems(
+                Update,
+                (
+                    beats::fade_pulses,
+                    playlist::queue_track,
+                    playlist::despawn_faded,
+                    playlist::fade_toasts,
+                ),
+            )
+            .add_observer(playlist::crossfade_on_beat)
+            .add_systems(OnEnter(GameState::Playing), director::reset_director)
+            .add_systems(
+                OnExit(GameState::Playing),
+                (director::reset_director, director::release_music),
+            )
+            .add_systems(
+                Update,
+                (director::measure_intensity, director::steer_music)
+                    .chain()
+                    .in_set(Playing),
+            )
+            .add_observer(beats::pulse_on_beat)
             .add_tween_systems((
                 component_tween_system::<InterpolateSampleSpeed>(),
                 component_dyn_tween_system::<PlaybackSettings>(),
@@ -36,56 +68,62 @@ impl Plugin for MusicPlugin {
                 component_dyn_tween_system::<VolumeNode>(),
             ))
             .add_systems(OnEnter(GameState::StartGame), tween_music_start)
-            .add_systems(OnEnter(GameState::Leaderboard), tween_music_lose);
+            .add_systems(OnEnter(GameState::Leaderboard), tween_music_lose)
+            .add_systems(OnEnter(PauseState::Paused), muffle_music)
+            .add_systems(OnExit(PauseState::Paused), unmuffle_music);
     }
 }
 
-#[derive(PoolLabel, PartialEq, Eq, Hash, Clone, Debug)]
-struct MusicPool;
-
 #[derive(Component)]
 struct MusicLowPass;
 
+/// Separate from [`MusicLowPass`] so pausing doesn't fight its tweens.
+#[derive(Component)]
+struct PauseLowPass;
+
 const OUTSIDE_FREQ: f32 = 100.0;
+const PAUSED_FREQ: f32 = 600.0;
+const OPEN_FREQ: f32 = 20_000.0;
 
-fn spawn_music(mut commands: Commands, server: Res<AssetServer>) {
-    let music_target = commands
+fn spawn_music(mut commands: Commands) {
+    let chain = commands
         .spawn((
-            SamplerPool(MusicPool),
-            VolumeNode {
-                volume: Volume::SILENT,
+            LowPassNode {
+                frequency: OUTSIDE_FREQ,
             },
+            MusicLowPass,
         ))
         .chain_node((
             LowPassNode {
-                frequency: OUTSIDE_FREQ,
+                frequency: OPEN_FREQ,
             },
-            MusicLowPass,
+            PauseLowPass,
+        ))
+        .chain_node((
+            LowPassNode {
+                frequency: OPEN_FREQ,
+            },
+            director::IntensityLowPass,
         ))
+        .chain_node((VolumeNode::default(), director::IntensityVolume))
+        .chain_node((VolumeNode::default(), Bus::Music))
         .head();
 
-    // fade in music
-    let mut target = music_target.into_target().state(-96.0);
-    commands.animation().insert(tween(
-        Duration::from_secs(1),
-        EaseKind::QuadraticOut,
-        target.with(interpolators::volume_to(0.0)),
-    ));
-
-    commands.spawn((
-        MusicPool,
-        MusicBeats::new(100.0),
-        SamplePlayer {
-            sample: server.load("audio/music/pinball-nightclub.ogg"),
-            repeat_mode: RepeatMode::RepeatEndlessly,
-            volume: Volume::Decibels(-6.0),
-        },
-        PlaybackSettings::default(),
-    ));
+    // Tracks crossfade between the two decks, which start silent.
+    let silent = || VolumeNode {
+        volume: Volume::SILENT,
+    };
+    commands
+        .spawn((SamplerPool(MusicPool), playlist::Deck::A, silent()))
+        .connect(chain);
+    commands
+        .spawn((SamplerPool(MusicFadePool), playlist::Deck::B, silent()))
+        .connect(chain);
+    commands.spawn(SamplerPool(MusicCuePool)).connect(chain);
 }
 
 fn tween_music_start(
-    music: Single<(Entity, &PlaybackSettings), (With<MusicBeats>, With<MusicPool>)>,
+    music: Single<(Entity, &PlaybackSettings), With<CurrentTrack>>,
     low_pass: Single<(Entity, &LowPassNode), With<MusicLowPass>>,
     mut commands: Commands,
 ) {
@@ -104,12 +142,12 @@ fn tween_music_start(
     commands.animation().insert(tween(
         Duration::from_secs(1),
         EaseKind::QuadraticInOut,
-        target.with(interpolators::low_pass_to(20_000.0)),
+        target.with(interpolators::low_pass_to(OPEN_FREQ)),
     ));
 }
 
 fn tween_music_lose(
-    music: Single<(Entity, &PlaybackSettings), (With<MusicBeats>, With<MusicPool>)>,
+    music: Single<(Entity, &PlaybackSettings), With<CurrentTrack>>,
     mut commands: Commands,
 ) {
     let (target, settings) = music.into_inner();
@@ -121,3 +159,12 @@ fn tween_music_lose(
         target.with(interpolators::sample_speed_to(0.4)),
     ));
 }
+
+// Tweens run on virtual time, which is frozen while paused, so these apply instantly.
+fn muffle_music(mut low_pass: Single<&mut LowPassNode, With<PauseLowPass>>) {
+    low_pass.frequency = PAUSED_FREQ;
+}
+
+fn unmuffle_music(mut low_pass: Single<&mut LowPassNode, With<PauseLowPass>>) {
+    low_pass.frequency = OPEN_FREQ;
+}
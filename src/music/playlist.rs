@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
+use bevy_seedling::prelude::*;
+use bevy_tween::{
+    combinator::tween,
+    prelude::{AnimationBuilderExt, EaseKind},
+    tween::IntoTarget,
+};
+
+use super::beats::{MusicBeats, OnBeat};
+use super::interpolators;
+use crate::audio::{MusicFadePool, MusicPool};
+use crate::collectables::POINT_COLOR;
+use crate::loading::RonAsset;
+use crate::state::GameState;
+
+/// Which tracks play where, loaded from `audio/music/music.playlist.ron`.
+#[derive(Asset, TypePath, serde::Deserialize)]
+pub struct Playlist {
+    tracks: HashMap<String, Track>,
+    menu: Vec<String>,
+    shop: Vec<String>,
+    play: Vec<String>,
+}
+
+impl RonAsset for Playlist {
+    const EXTENSIONS: &'static [&'static str] = &["playlist.ron"];
+}
+
+impl Playlist {
+    fn slot(&self, slot: MusicSlot) -> &[String] {
+        match slot {
+            MusicSlot::Menu => &self.menu,
+            MusicSlot::Shop => &self.shop,
+            MusicSlot::Play => &self.play,
+        }
+    }
+}
+
+/// A piece of music and the timing of its beat.
+#[derive(Debug, Clone, serde::Deserialize)]
+struct Track {
+    path: String,
+    title: String,
+    bpm: f32,
+    /// Seconds from the start of the file to the first beat.
+    #[serde(default)]
+    offset: f32,
+    #[serde(default = "Track::default_beats_per_bar")]
+    beats_per_bar: u32,
+    #[serde(default = "Track::default_volume")]
+    volume: f32,
+}
+
+impl Track {
+    fn default_beats_per_bar() -> u32 {
+        4
+    }
+
+    fn default_volume() -> f32 {
+        -6.
+    }
+
+    fn beats(&self) -> MusicBeats {
+        MusicBeats::new(self.bpm)
+            .with_offset(self.offset)
+            .with_beats_per_bar(self.beats_per_bar)
+    }
+
+    fn bar_secs(&self) -> f32 {
+        60. / self.bpm * self.beats_per_bar as f32
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum MusicSlot {
+    Menu,
+    Shop,
+    Play,
+}
+
+impl MusicSlot {
+    /// The slot for a state, or `None` while passing between states.
+    fn of(state: &GameState) -> Option<Self> {
+        match state {
+            GameState::Loading
+            | GameState::Menu
+            | GameState::Leaderboard
+            | GameState::Initials
+            | GameState::Achievements
+            | GameState::Controls => Some(Self::Menu),
+            GameState::Selection => Some(Self::Shop),
+            GameState::StartGame | GameState::Playing => Some(Self::Play),
+            GameState::Reset => None,
+        }
+    }
+}
+
+/// The music player that beats, speed tweens and the director follow.
+///
+/// Only one player has it at a time, the others are fading out.
+#[derive(Component)]
+pub struct CurrentTrack;
+
+/// One of the two music pools that tracks crossfade between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub(super) enum Deck {
+    A,
+    B,
+}
+
+impl Deck {
+    fn other(&self) -> Self {
+        match self {
+            Self::A => Self::B,
+            Self::B => Self::A,
+        }
+    }
+}
+
+/// A player on its way out, despawned once its deck is silent.
+#[derive(Component)]
+struct FadeOut(Timer);
+
+#[derive(Component)]
+struct NowPlaying(Timer);
+
+#[derive(Resource)]
+pub(super) struct Jukebox {
+    playlist: Handle<Playlist>,
+    /// The current track and the length of its bar, in seconds.
+    current: Option<(String, f32)>,
+    queued: Option<String>,
+    deck: Deck,
+    /// The next track to pick in each slot.
+    rotation: HashMap<MusicSlot, usize>,
+    /// The volume tweens of the running crossfade.
+    fades: Vec<Entity>,
+}
+
+/// How long the very first track takes to fade in.
+const FIRST_FADE: f32 = 1.;
+const TOAST_SECS: f32 = 3.;
+const TOAST_FADE_SECS: f32 = 0.5;
+
+pub(super) fn load_playlist(mut commands: Commands, server: Res<AssetServer>) {
+    commands.insert_resource(Jukebox {
+        playlist: server.load("audio/music/music.playlist.ron"),
+        current: None,
+        queued: None,
+        deck: Deck::B,
+        rotation: HashMap::default(),
+        fades: Vec::new(),
+    });
+}
+
+/// Queues a track from the slot of the current state, unless one is already playing.
+pub(super) fn queue_track(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    mut jukebox: ResMut<Jukebox>,
+    playlists: Res<Assets<Playlist>>,
+    mut loaded: Local<bool>,
+) {
+    let Some(playlist) = playlists.get(&jukebox.playlist) else {
+        return;
+    };
+    if !state.is_changed() && *loaded {
+        return;
+    }
+    *loaded = true;
+
+    let Some(slot) = MusicSlot::of(state.get()) else {
+        return;
+    };
+    let tracks = playlist.slot(slot);
+    if tracks.is_empty() {
+        return;
+    }
+
+    if jukebox
+        .current
+        .as_ref()
+        .is_some_and(|(name, _)| tracks.contains(name))
+    {
+        jukebox.queued = None;
+        return;
+    }
+
+    let index = jukebox.rotation.entry(slot).or_default();
+    let name = tracks[*index % tracks.len()].clone();
+    *index += 1;
+
+    // Nothing to line up with yet, so the first track starts right away.
+    if jukebox.current.is_none() {
+        commands.run_system_cached_with(start_track, name);
+    } else {
+        jukebox.queued = Some(name);
+    }
+}
+
+/// Crossfades to the queued track on the next downbeat.
+pub(super) fn crossfade_on_beat(
+    trigger: Trigger<OnBeat>,
+    mut commands: Commands,
+    mut jukebox: ResMut<Jukebox>,
+) {
+    if trigger.beat != 0 {
+        return;
+    }
+
+    if let Some(name) = jukebox.queued.take() {
+        commands.run_system_cached_with(start_track, name);
+    }
+}
+
+fn start_track(
+    In(name): In<String>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    mut jukebox: ResMut<Jukebox>,
+    playlists: Res<Assets<Playlist>>,
+    decks: Query<(Entity, &Deck, &VolumeNode)>,
+    current: Query<Entity, With<CurrentTrack>>,
+    fading: Query<(Entity, Has<MusicPool>), With<FadeOut>>,
+    toasts: Query<Entity, With<NowPlaying>>,
+) {
+    let Some(track) = playlists
+        .get(&jukebox.playlist)
+        .and_then(|playlist| playlist.tracks.get(&name))
+    else {
+        warn!("no music track named `{name}`");
+        return;
+    };
+
+    // The fade lasts a bar of the outgoing track, so it ends on a downbeat too.
+    let fade = match &jukebox.current {
+        Some((_, bar_secs)) => *bar_secs,
+        None => FIRST_FADE,
+    };
+    jukebox.current = Some((name, track.bar_secs()));
+    jukebox.deck = jukebox.deck.other();
+
+    // A player still fading out of the incoming deck would be faded back up with it.
+    for (entity, on_a) in fading.iter() {
+        let deck = if on_a { Deck::A } else { Deck::B };
+        if deck == jukebox.deck {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for entity in current.iter() {
+        commands
+            .entity(entity)
+            .remove::<CurrentTrack>()
+            .insert(FadeOut(Timer::from_seconds(fade, TimerMode::Once)));
+    }
+
+    let player = (
+        CurrentTrack,
+        track.beats(),
+        SamplePlayer {
+            sample: server.load(track.path.clone()),
+            repeat_mode: RepeatMode::RepeatEndlessly,
+            volume: Volume::Decibels(track.volume),
+        },
+        PlaybackSettings::default(),
+    );
+    match jukebox.deck {
+        Deck::A => commands.spawn((MusicPool, player)),
+        Deck::B => commands.spawn((MusicFadePool, player)),
+    };
+
+    // A crossfade cut short would otherwise keep fighting the new one over the decks.
+    for tween in jukebox.fades.drain(..) {
+        commands.entity(tween).try_despawn();
+    }
+    for (entity, deck, node) in decks.iter() {
+        let to = if *deck == jukebox.deck { 0. } else { -96. };
+        let mut target = entity.into_target().state(node.volume.decibels());
+        let tween = commands
+            .animation()
+            .insert(tween(
+                Duration::from_secs_f32(fade),
+                EaseKind::QuadraticOut,
+                target.with(interpolators::volume_to(to)),
+            ))
+            .id();
+        jukebox.fades.push(tween);
+    }
+
+    for entity in toasts.iter() {
+        commands.entity(entity).despawn();
+    }
+    commands.spawn((
+        NowPlaying(Timer::from_seconds(TOAST_SECS, TimerMode::Once)),
+        HIGH_RES_LAYER,
+        Text2d::new(format!("NOW PLAYING  {}", track.title)),
+        TextFont {
+            font_size: 18.,
+            font: server.load("fonts/saiba.ttf"),
+            ..Default::default()
+        },
+        TextColor(POINT_COLOR.into()),
+        Anchor::BottomLeft,
+        Transform::from_xyz(
+            -crate::RES_WIDTH * 0.75 + 16.,
+            -crate::RES_HEIGHT / 2. + 12.,
+            900.,
+        ),
+    ));
+}
+
+pub(super) fn despawn_faded(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut players: Query<(Entity, &mut FadeOut)>,
+) {
+    for (entity, mut fade) in players.iter_mut() {
+        if fade.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Toasts run on real time so they still leave while the game is paused.
+pub(super) fn fade_toasts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut toasts: Query<(Entity, &mut NowPlaying, &mut TextColor)>,
+) {
+    for (entity, mut toast, mut color) in toasts.iter_mut() {
+        toast.0.tick(time.delta());
+        if toast.0.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let alpha = (toast.0.remaining_secs() / TOAST_FADE_SECS).min(1.);
+        color.0.set_alpha(alpha);
+    }
+}
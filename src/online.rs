@@ -0,0 +1,491 @@
+//! Optional client for a global leaderboard server.
+//!
+//! The client is disabled unless `SLUGBALL_LEADERBOARD_URL` is set, either at runtime
+//! or at compile time for web builds. Every request is JSON, so a throwaway local
+//! server can stand in for the real one.
+//!
+//! # Protocol
+//!
+//! `POST {base}/v1/runs` with a [`RunSubmission`]:
+//!
+//! ```json
+//! {
+//!   "initials": "ABC",
+//!   "score": "1250",
+//!   "level": 3,
+//!   "seed": 42,
+//!   "timestamp": 1750000000,
+//!   "mode": "Standard",
+//!   "replay_hash": "9f2c6d1e0a4b7c38"
+//! }
+//! ```
+//!
+//! `score` is a decimal string because it routinely exceeds what JSON numbers can hold.
+//! Any 2xx response accepts the run. 4xx responses reject it for good, except for 408
+//! and 429, which like anything else (including network errors) leave it in the on-disk
+//! queue to be retried.
+//!
+//! `GET {base}/v1/top?limit={n}` responds with a [`TopList`]:
+//!
+//! ```json
+//! { "entries": [{ "initials": "ABC", "score": "1250", "level": 3, "timestamp": 1750000000 }] }
+//! ```
+
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::mpsc::{Receiver, Sender, channel};
+
+use bevy::prelude::*;
+use bevy_persistent::prelude::*;
+
+use crate::leaderboard::{LEADERBOARD_SIZE, LeaderboardEntry, RunRecorded};
+use crate::state::{GameMode, GameState};
+
+const URL_VAR: &str = "SLUGBALL_LEADERBOARD_URL";
+const RETRY_SECS: f32 = 30.;
+
+pub struct OnlinePlugin;
+
+impl Plugin for OnlinePlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = channel();
+        let mut retry = Timer::from_seconds(RETRY_SECS, TimerMode::Once);
+        // Send whatever is queued from the last session right away.
+        retry.tick(retry.duration());
+
+        app.insert_resource(OnlineLeaderboard::from_env())
+            .insert_resource(Inbox {
+                sender,
+                receiver: Mutex::new(receiver),
+            })
+            .insert_resource(GlobalTop::Disabled)
+            .insert_resource(Outbox {
+                in_flight: false,
+                retry,
+            })
+            .add_systems(Startup, submission_queue)
+            .add_systems(OnEnter(GameState::Leaderboard), fetch_top)
+            .add_systems(Update, (queue_runs, send_queue, receive).chain());
+    }
+}
+
+/// Where the global leaderboard lives. `None` keeps the game fully offline.
+#[derive(Resource)]
+pub struct OnlineLeaderboard {
+    pub base_url: Option<String>,
+}
+
+impl OnlineLeaderboard {
+    fn from_env() -> Self {
+        let base_url = std::env::var(URL_VAR)
+            .ok()
+            .or_else(|| option_env!("SLUGBALL_LEADERBOARD_URL").map(String::from))
+            .map(|url| url.trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty());
+
+        Self { base_url }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RunSubmission {
+    pub initials: String,
+    pub score: String,
+    pub level: usize,
+    pub seed: u64,
+    pub timestamp: u64,
+    pub mode: GameMode,
+    pub replay_hash: String,
+}
+
+impl RunSubmission {
+    pub fn new(entry: &LeaderboardEntry) -> Self {
+        Self {
+            initials: entry.initials.clone(),
+            score: entry.points.0.to_string(),
+            level: entry.level,
+            seed: entry.seed,
+            timestamp: entry.timestamp,
+            mode: entry.mode,
+            replay_hash: replay_hash(entry),
+        }
+    }
+}
+
+/// Fingerprint of a run so the server can spot duplicate or tampered submissions.
+///
+/// FNV-1a over the fields that define the run, stable across platforms and builds.
+fn replay_hash(entry: &LeaderboardEntry) -> String {
+    let canonical = format!(
+        "{}|{}|{}|{}|{}|{}",
+        entry.seed,
+        entry.level,
+        entry.points.0,
+        entry.timestamp,
+        entry.initials,
+        entry.features.join(",")
+    );
+
+    let hash = canonical.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TopList {
+    pub entries: Vec<TopEntry>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TopEntry {
+    pub initials: String,
+    pub score: String,
+    pub level: usize,
+    pub timestamp: u64,
+}
+
+/// The latest global top list, as shown on the leaderboard's Global tab.
+#[derive(Resource)]
+pub enum GlobalTop {
+    Disabled,
+    Loading,
+    Failed,
+    Loaded(TopList),
+}
+
+/// Runs that have not reached the server yet, oldest first.
+#[derive(Default, Resource, serde::Serialize, serde::Deserialize)]
+struct SubmissionQueue(Vec<RunSubmission>);
+
+fn submission_queue(mut commands: Commands) {
+    let build = |path: &Path| {
+        Persistent::<SubmissionQueue>::builder()
+            .name("submission queue")
+            .format(StorageFormat::Bincode)
+            .path(path)
+            .default(Default::default())
+            .revert_to_default_on_deserialization_errors(true)
+            .build()
+    };
+
+    let queue = build(&crate::save::save_dir().join("submissions"));
+    commands.insert_resource(crate::save::or_scratch(
+        queue,
+        "submissions",
+        "submission queue",
+        build,
+    ));
+}
+
+enum Reply {
+    Accepted(RunSubmission),
+    Rejected(RunSubmission),
+    Unreachable,
+    Top(Option<TopList>),
+}
+
+/// Collects responses from the request threads.
+#[derive(Resource)]
+struct Inbox {
+    sender: Sender<Reply>,
+    receiver: Mutex<Receiver<Reply>>,
+}
+
+/// Submissions are sent one at a time, the queue pauses for [`RETRY_SECS`] after a failure.
+#[derive(Resource)]
+struct Outbox {
+    in_flight: bool,
+    retry: Timer,
+}
+
+fn queue_runs(
+    mut reader: EventReader<RunRecorded>,
+    mut queue: ResMut<Persistent<SubmissionQueue>>,
+    online: Res<OnlineLeaderboard>,
+) {
+    if online.base_url.is_none() {
+        reader.clear();
+        return;
+    }
+
+    if reader.is_empty() {
+        return;
+    }
+
+    for run in reader.read() {
+        queue.0.push(RunSubmission::new(&run.0));
+    }
+
+    if let Err(e) = queue.persist() {
+        error!("failed to save submission queue: {e}");
+    }
+}
+
+fn send_queue(
+    time: Res<Time<Real>>,
+    online: Res<OnlineLeaderboard>,
+    queue: Res<Persistent<SubmissionQueue>>,
+    inbox: Res<Inbox>,
+    mut outbox: ResMut<Outbox>,
+) {
+    let Some(base_url) = &online.base_url else {
+        return;
+    };
+
+    outbox.retry.tick(time.delta());
+    if outbox.in_flight || !outbox.retry.finished() {
+        return;
+    }
+
+    let Some(submission) = queue.0.first().cloned() else {
+        return;
+    };
+
+    let request = match submit_request(base_url, &submission) {
+        Ok(request) => request,
+        Err(e) => {
+            error!("failed to encode run submission: {e}");
+            return;
+        }
+    };
+
+    outbox.in_flight = true;
+    let sender = inbox.sender.clone();
+    ehttp::fetch(request, move |result| {
+        let _ = sender.send(submit_reply(submission, result));
+    });
+}
+
+fn submit_request(
+    base_url: &str,
+    submission: &RunSubmission,
+) -> Result<ehttp::Request, serde_json::Error> {
+    ehttp::Request::json(format!("{base_url}/v1/runs"), submission)
+}
+
+fn submit_reply(submission: RunSubmission, result: ehttp::Result<ehttp::Response>) -> Reply {
+    match result {
+        Ok(response) if response.ok => Reply::Accepted(submission),
+        Ok(response) if rejected(response.status) => Reply::Rejected(submission),
+        _ => Reply::Unreachable,
+    }
+}
+
+/// Whether the server turned a run down for good. Timeouts and rate limits are only
+/// worth waiting out.
+fn rejected(status: u16) -> bool {
+    (400..500).contains(&status) && !matches!(status, 408 | 429)
+}
+
+fn top_request(base_url: &str) -> ehttp::Request {
+    ehttp::Request::get(format!("{base_url}/v1/top?limit={LEADERBOARD_SIZE}"))
+}
+
+fn top_reply(result: ehttp::Result<ehttp::Response>) -> Option<TopList> {
+    result
+        .ok()
+        .filter(|response| response.ok)
+        .and_then(|response| response.json::<TopList>().ok())
+}
+
+fn fetch_top(online: Res<OnlineLeaderboard>, inbox: Res<Inbox>, mut top: ResMut<GlobalTop>) {
+    let Some(base_url) = &online.base_url else {
+        *top = GlobalTop::Disabled;
+        return;
+    };
+
+    *top = GlobalTop::Loading;
+    let sender = inbox.sender.clone();
+    ehttp::fetch(top_request(base_url), move |result| {
+        let _ = sender.send(Reply::Top(top_reply(result)));
+    });
+}
+
+fn receive(
+    inbox: Res<Inbox>,
+    mut outbox: ResMut<Outbox>,
+    mut queue: ResMut<Persistent<SubmissionQueue>>,
+    mut top: ResMut<GlobalTop>,
+) {
+    let Ok(receiver) = inbox.receiver.lock() else {
+        return;
+    };
+
+    for reply in receiver.try_iter() {
+        match reply {
+            Reply::Accepted(submission) | Reply::Rejected(submission) => {
+                outbox.in_flight = false;
+                queue.0.retain(|queued| *queued != submission);
+                if let Err(e) = queue.persist() {
+                    error!("failed to save submission queue: {e}");
+                }
+            }
+            Reply::Unreachable => {
+                outbox.in_flight = false;
+                outbox.retry.reset();
+            }
+            Reply::Top(Some(list)) => *top = GlobalTop::Loaded(list),
+            Reply::Top(None) => *top = GlobalTop::Failed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    use super::*;
+
+    /// Serves `requests` requests on a local port, answering each with `respond`, which
+    /// gets the method, path and body and returns a status and body.
+    fn mock_server(
+        requests: usize,
+        mut respond: impl FnMut(&str, &str, &str) -> (u16, String) + Send + 'static,
+    ) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut parts = request_line.split_whitespace();
+                let method = parts.next().unwrap_or_default().to_string();
+                let path = parts.next().unwrap_or_default().to_string();
+
+                let mut length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let header = header.trim();
+                    if header.is_empty() {
+                        break;
+                    }
+                    let Some((name, value)) = header.split_once(':') else {
+                        continue;
+                    };
+                    if name.eq_ignore_ascii_case("content-length") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+
+                let (status, body) = respond(&method, &path, &String::from_utf8_lossy(&body));
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} MOCK\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        base_url
+    }
+
+    fn submission(initials: &str) -> RunSubmission {
+        RunSubmission {
+            initials: String::from(initials),
+            score: String::from("1250"),
+            level: 3,
+            seed: 42,
+            timestamp: 1_750_000_000,
+            mode: GameMode::Standard,
+            replay_hash: String::from("9f2c6d1e0a4b7c38"),
+        }
+    }
+
+    #[test]
+    fn submit_and_fetch_round_trip() {
+        let mut runs = Vec::<RunSubmission>::new();
+        let base_url = mock_server(2, move |method, path, body| match (method, path) {
+            ("POST", "/v1/runs") => {
+                runs.push(serde_json::from_str(body).unwrap());
+                (201, String::new())
+            }
+            ("GET", path) if path == format!("/v1/top?limit={LEADERBOARD_SIZE}") => {
+                let entries = runs
+                    .iter()
+                    .map(|run| TopEntry {
+                        initials: run.initials.clone(),
+                        score: run.score.clone(),
+                        level: run.level,
+                        timestamp: run.timestamp,
+                    })
+                    .collect();
+                (200, serde_json::to_string(&TopList { entries }).unwrap())
+            }
+            _ => (404, String::new()),
+        });
+
+        let run = submission("ABC");
+        let request = submit_request(&base_url, &run).unwrap();
+        let reply = submit_reply(run.clone(), ehttp::fetch_blocking(&request));
+        assert!(matches!(reply, Reply::Accepted(accepted) if accepted == run));
+
+        let top = top_reply(ehttp::fetch_blocking(&top_request(&base_url))).unwrap();
+        assert_eq!(top.entries.len(), 1);
+        assert_eq!(top.entries[0].initials, "ABC");
+        assert_eq!(top.entries[0].score, "1250");
+    }
+
+    #[test]
+    fn submit_retries_timeouts_and_rate_limits() {
+        let mut statuses = vec![400, 408, 429, 503].into_iter();
+        let base_url = mock_server(4, move |_, _, _| (statuses.next().unwrap(), String::new()));
+
+        let run = submission("ABC");
+        let mut send = || {
+            let request = submit_request(&base_url, &run).unwrap();
+            submit_reply(run.clone(), ehttp::fetch_blocking(&request))
+        };
+
+        assert!(matches!(send(), Reply::Rejected(_)));
+        assert!(matches!(send(), Reply::Unreachable));
+        assert!(matches!(send(), Reply::Unreachable));
+        assert!(matches!(send(), Reply::Unreachable));
+    }
+
+    #[test]
+    fn fetch_fails_without_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        assert!(top_reply(ehttp::fetch_blocking(&top_request(&base_url))).is_none());
+    }
+
+    #[test]
+    fn submission_schema() {
+        assert_eq!(
+            serde_json::to_value(submission("ABC")).unwrap(),
+            serde_json::json!({
+                "initials": "ABC",
+                "score": "1250",
+                "level": 3,
+                "seed": 42,
+                "timestamp": 1750000000,
+                "mode": "Standard",
+                "replay_hash": "9f2c6d1e0a4b7c38",
+            })
+        );
+    }
+
+    #[test]
+    fn top_list_schema() {
+        let list: TopList = serde_json::from_value(serde_json::json!({
+            "entries": [{ "initials": "ABC", "score": "1250", "level": 3, "timestamp": 1750000000 }]
+        }))
+        .unwrap();
+
+        assert_eq!(list.entries.len(), 1);
+        assert_eq!(list.entries[0].score, "1250");
+    }
+}
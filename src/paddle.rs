@@ -3,10 +3,6 @@ use std::{f32::consts::PI, time::Duration};
 use avian2d::prelude::*;
 use bevy::{prelude::*, time::Stopwatch};
 use bevy_enhanced_input::events::Fired;
-use bevy_seedling::{
-    prelude::Volume,
-    sample::{PitchRange, SamplePlayer},
-};
 use bevy_tween::{
     prelude::{AnimationBuilderExt, EaseKind},
     tween::IntoTarget,
@@ -14,6 +10,7 @@ use bevy_tween::{
 
 use crate::{
     Avian, Layer,
+    audio::PlaySound,
     ball::{PaddleRestMult, paddle_mult},
     input::{PaddleDown, PaddleUp},
     state::{GameState, StateAppExt, remove_entities},
@@ -34,7 +31,8 @@ impl Plugin for PaddlePlugin {
             .add_systems(Update, paddle_rest)
             .add_systems(Avian, paddles.before(PhysicsSet::Prepare))
             .add_observer(apply_pressed)
-            .add_observer(apply_released);
+            .add_observer(apply_released)
+            .add_observer(flip_paddles);
 
         #[cfg(debug_assertions)]
         {
@@ -154,62 +152,84 @@ struct PaddleTarget(Quat);
 const PADDLE_SPEED: f32 = 20.0;
 const PADDLE_DOWN_SPEED: f32 = PADDLE_SPEED * 0.8;
 
-fn apply_pressed(
-    _trigger: Trigger<Fired<PaddleUp>>,
-    paddles: Query<(Entity, &Transform), With<Paddle>>,
-    mut rest: Single<&mut PaddleRest>,
-    mut commands: Commands,
-    server: Res<AssetServer>,
-) {
-    rest.0.pause();
-
-    commands.spawn((
-        SamplePlayer::new(server.load("audio/pinball/FlipperUp.ogg"))
-            .with_volume(Volume::Decibels(-12.0)),
-        PitchRange(0.99..1.01),
-    ));
+/// Which half of the cabinet a [`Paddle`] sits in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaddleSide {
+    Left,
+    Right,
+}
 
-    for (entity, position) in paddles.iter() {
-        let sign = if position.translation.x > 0.0 {
-            -1.
+impl PaddleSide {
+    fn of(transform: &Transform) -> Self {
+        if transform.translation.x > 0.0 {
+            Self::Right
         } else {
-            1.
-        };
-
-        commands.entity(entity).insert((
-            AngularVelocity(sign * PADDLE_SPEED),
-            PaddleTarget(Quat::from_rotation_z(sign * (START_ROT + END_OFFSET))),
-        ));
+            Self::Left
+        }
     }
 }
 
-fn apply_released(
-    _trigger: Trigger<Fired<PaddleDown>>,
+/// Raises or lowers the paddles on `side`, or both if `None`.
+#[derive(Event)]
+pub struct FlipPaddles {
+    pub side: Option<PaddleSide>,
+    pub up: bool,
+}
+
+fn apply_pressed(_trigger: Trigger<Fired<PaddleUp>>, mut commands: Commands) {
+    commands.trigger(FlipPaddles {
+        side: None,
+        up: true,
+    });
+}
+
+fn apply_released(_trigger: Trigger<Fired<PaddleDown>>, mut commands: Commands) {
+    commands.trigger(FlipPaddles {
+        side: None,
+        up: false,
+    });
+}
+
+fn flip_paddles(
+    trigger: Trigger<FlipPaddles>,
     paddles: Query<(Entity, &Transform), With<Paddle>>,
     mut rest: Single<&mut PaddleRest>,
     mut commands: Commands,
-    server: Res<AssetServer>,
+    mut sounds: EventWriter<PlaySound>,
 ) {
-    rest.0.reset();
-    rest.0.unpause();
+    let flip = trigger.event();
+    if flip.up {
+        rest.0.pause();
+    } else {
+        rest.0.reset();
+        rest.0.unpause();
+    }
 
-    commands.spawn((
-        SamplePlayer::new(server.load("audio/pinball/FlipperDown.ogg"))
-            .with_volume(Volume::Decibels(-12.0)),
-        PitchRange(0.99..1.01),
-    ));
+    let cue = if flip.up { "paddle.up" } else { "paddle.down" };
+    sounds.write(PlaySound::new(cue));
 
     for (entity, position) in paddles.iter() {
-        let sign = if position.translation.x > 0.0 {
-            -1.
-        } else {
-            1.
+        let side = PaddleSide::of(position);
+        if flip.side.is_some_and(|s| s != side) {
+            continue;
+        }
+
+        let sign = match side {
+            PaddleSide::Right => -1.,
+            PaddleSide::Left => 1.,
         };
 
-        commands.entity(entity).insert((
-            AngularVelocity(-sign * PADDLE_DOWN_SPEED),
-            PaddleTarget(Quat::from_rotation_z(sign * START_ROT)),
-        ));
+        if flip.up {
+            commands.entity(entity).insert((
+                AngularVelocity(sign * PADDLE_SPEED),
+                PaddleTarget(Quat::from_rotation_z(sign * (START_ROT + END_OFFSET))),
+            ));
+        } else {
+            commands.entity(entity).insert((
+                AngularVelocity(-sign * PADDLE_DOWN_SPEED),
+                PaddleTarget(Quat::from_rotation_z(sign * START_ROT)),
+            ));
+        }
     }
 }
 
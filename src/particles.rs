@@ -6,12 +6,21 @@ use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
 use bevy_enoki::prelude::*;
 
+use crate::ball::BallComponents;
+
 pub struct ParticlePlugin;
 
 impl Plugin for ParticlePlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(ParticleSpriteHash::default())
-            .register_particle_state::<Always>();
+            .init_resource::<ParticleBudget>()
+            .add_event::<Burst>()
+            .register_particle_state::<Always>()
+            .add_systems(
+                PostUpdate,
+                (measure_budget, spawn_bursts).chain().before(ParticleSet),
+            )
+            .add_systems(Update, despawn_bursts);
     }
 }
 
@@ -114,6 +123,94 @@ pub fn transform(transform: Transform) -> impl FnMut(&mut EntityCommands) {
     }
 }
 
+/// Spawns a one-shot effect at `position`, cleaned up once its particles have died.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct Burst {
+    pub effect: &'static str,
+    pub position: Vec2,
+}
+
+impl Burst {
+    pub fn new(effect: &'static str, position: Vec2) -> Self {
+        Self { effect, position }
+    }
+}
+
+/// Holds the emitters of a [`Burst`].
+#[derive(Component)]
+struct BurstEmitter(Timer);
+
+/// Bursts draw over the features and balls.
+const BURST_Z: f32 = 10.;
+
+/// Longest a burst may live, in case its emitter never reports finishing.
+const MAX_BURST_SECS: f32 = 4.;
+
+/// Number of live balls above which bursts start being thinned out.
+const BUDGET_BALLS: f32 = 200.;
+
+/// Share of requested bursts that are spawned, so thousands of balls don't
+/// bury the playfield in particles.
+#[derive(Resource)]
+struct ParticleBudget {
+    scale: f32,
+    /// Carries the fraction of a burst between requests, thinning them evenly.
+    credit: f32,
+}
+
+impl Default for ParticleBudget {
+    fn default() -> Self {
+        Self {
+            scale: 1.,
+            credit: 0.,
+        }
+    }
+}
+
+fn measure_budget(mut budget: ResMut<ParticleBudget>, balls: Query<(), With<BallComponents>>) {
+    let balls = balls.iter().count() as f32;
+    budget.scale = (BUDGET_BALLS / balls.max(1.)).min(1.);
+}
+
+fn spawn_bursts(
+    mut commands: Commands,
+    mut reader: EventReader<Burst>,
+    mut budget: ResMut<ParticleBudget>,
+) {
+    for burst in reader.read() {
+        budget.credit = (budget.credit + budget.scale).min(1.);
+        if budget.credit < 1. {
+            continue;
+        }
+        budget.credit -= 1.;
+
+        commands.spawn((
+            BurstEmitter(Timer::from_seconds(MAX_BURST_SECS, TimerMode::Once)),
+            ParticleBundle::<Always>::from_emitter(
+                ParticleEmitter::from_effect(burst.effect).with(|commands: &mut EntityCommands| {
+                    commands.insert(OneShot::Despawn);
+                }),
+            ),
+            Transform::from_translation(burst.position.extend(BURST_Z)),
+            Visibility::default(),
+        ));
+    }
+}
+
+/// Despawns bursts whose emitters have despawned themselves.
+fn despawn_bursts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut bursts: Query<(Entity, &mut BurstEmitter, Option<&Children>)>,
+) {
+    for (entity, mut burst, children) in bursts.iter_mut() {
+        let timeout = burst.0.tick(time.delta()).finished();
+        if timeout || children.is_none_or(|children| children.is_empty()) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
 #[derive(Default, Resource)]
 struct ParticleSpriteHash(HashMap<SpriteMat, Handle<SpriteParticle2dMaterial>>);
 
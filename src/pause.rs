@@ -0,0 +1,349 @@
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowFocused};
+use bevy_enhanced_input::events::Fired;
+use bevy_optix::pixel_perfect::{HIGH_RES_LAYER, OuterCamera};
+use bevy_persistent::prelude::*;
+
+use crate::collectables::POINT_COLOR;
+use crate::focus::{Activate, Focusable, Focused};
+use crate::input::{Back, FocusLeft, FocusRight, Pause};
+use crate::settings::{Setting, Settings};
+use crate::state::{GameState, Playing, ResetTarget, remove_entities};
+use crate::touch::Taps;
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<PauseState>()
+            .insert_resource(PausePage::Main)
+            .configure_sets(PreUpdate, Playing.run_if(in_state(PauseState::Running)))
+            .configure_sets(Update, Playing.run_if(in_state(PauseState::Running)))
+            .configure_sets(PostUpdate, Playing.run_if(in_state(PauseState::Running)))
+            .add_systems(OnEnter(PauseState::Paused), spawn_pause)
+            .add_systems(
+                OnExit(PauseState::Paused),
+                remove_entities::<With<PauseScreen>>,
+            )
+            .add_systems(Update, pause_on_focus_loss.in_set(Playing))
+            .add_systems(
+                Update,
+                (spawn_rows, point_rows, select, update_rows)
+                    .chain()
+                    .run_if(in_state(PauseState::Paused)),
+            )
+            .add_observer(pause)
+            .add_observer(back)
+            .add_observer(adjust_left)
+            .add_observer(adjust_right);
+    }
+}
+
+/// Freezes a run in [`GameState::Playing`] without leaving it, so nothing tied to
+/// entering or exiting play runs on pause and resume.
+#[derive(States, Default, Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+impl PauseState {
+    /// Scales a time multiplier, stopping time entirely while paused.
+    pub fn scale(&self, mult: f32) -> f32 {
+        match self {
+            Self::Running => mult,
+            Self::Paused => 0.,
+        }
+    }
+}
+
+#[derive(Resource, Clone, Copy, PartialEq, Eq)]
+enum PausePage {
+    Main,
+    Settings,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PauseItem {
+    Resume,
+    Settings,
+    Restart,
+    Quit,
+    Setting(Setting),
+    Back,
+}
+
+impl PausePage {
+    fn items(&self) -> Vec<PauseItem> {
+        match self {
+            Self::Main => vec![
+                PauseItem::Resume,
+                PauseItem::Settings,
+                PauseItem::Restart,
+                PauseItem::Quit,
+            ],
+            Self::Settings => Setting::ALL
+                .into_iter()
+                .map(PauseItem::Setting)
+                .chain([PauseItem::Back])
+                .collect(),
+        }
+    }
+}
+
+#[derive(Component)]
+struct PauseScreen;
+
+#[derive(Component)]
+struct PauseRow(PauseItem);
+
+const SCREENZ: f32 = 950.;
+const FIRST_ROW: f32 = 40.;
+const ROW_SPACING: f32 = 44.;
+/// Rows are squeezed together rather than run past this.
+const LAST_ROW: f32 = -crate::RES_HEIGHT / 2. + 40.;
+
+fn pause(
+    _: Trigger<Fired<Pause>>,
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    pause: Res<State<PauseState>>,
+) {
+    if *state.get() == GameState::Playing && *pause.get() == PauseState::Running {
+        commands.set_state(PauseState::Paused);
+    }
+}
+
+fn pause_on_focus_loss(
+    mut commands: Commands,
+    mut reader: EventReader<WindowFocused>,
+    settings: Res<Persistent<Settings>>,
+) {
+    if reader.read().any(|event| !event.focused) && settings.pause_on_focus_loss {
+        commands.set_state(PauseState::Paused);
+    }
+}
+
+fn spawn_pause(mut commands: Commands, server: Res<AssetServer>, mut page: ResMut<PausePage>) {
+    *page = PausePage::Main;
+
+    commands.spawn((
+        PauseScreen,
+        HIGH_RES_LAYER,
+        Sprite::from_color(
+            Color::BLACK.with_alpha(0.7),
+            Vec2::new(crate::RES_WIDTH * 2., crate::RES_HEIGHT),
+        ),
+        Transform::from_xyz(0., 0., SCREENZ - 1.),
+    ));
+
+    commands.spawn((
+        PauseScreen,
+        HIGH_RES_LAYER,
+        Text2d::new("PAUSED"),
+        TextFont {
+            font_size: 54.,
+            font: server.load("fonts/saiba.ttf"),
+            ..Default::default()
+        },
+        Transform::from_xyz(0., crate::RES_HEIGHT / 3., SCREENZ),
+    ));
+}
+
+fn row_position(row: usize, rows: usize) -> Vec2 {
+    let spacing = ROW_SPACING.min((FIRST_ROW - LAST_ROW) / rows.saturating_sub(1).max(1) as f32);
+    Vec2::new(0., FIRST_ROW - row as f32 * spacing)
+}
+
+/// Rebuilds the rows whenever the page changes and focuses the first one.
+fn spawn_rows(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    page: Res<PausePage>,
+    rows: Query<Entity, With<PauseRow>>,
+) {
+    if !page.is_changed() {
+        return;
+    }
+
+    for entity in rows.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let items = page.items();
+    let count = items.len();
+    for (i, item) in items.into_iter().enumerate() {
+        let mut row = commands.spawn((
+            PauseScreen,
+            PauseRow(item),
+            Focusable::at(row_position(i, count)),
+            HIGH_RES_LAYER,
+            Text2d::default(),
+            TextFont {
+                font_size: 28.,
+                font: server.load("fonts/saiba.ttf"),
+                ..Default::default()
+            },
+            Transform::from_translation(row_position(i, count).extend(SCREENZ)),
+        ));
+        if i == 0 {
+            row.insert(Focused);
+        }
+    }
+}
+
+/// The cursor focuses the row under it, clicking or tapping a row confirms it.
+fn point_rows(
+    mut commands: Commands,
+    input: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<OuterCamera>>,
+    rows: Query<(Entity, &Focusable, Has<Focused>), With<PauseRow>>,
+    mut moved: EventReader<CursorMoved>,
+    mut taps: Taps,
+    mut writer: EventWriter<Activate>,
+) {
+    let moved = moved.read().count() > 0;
+    let over = |point: Vec2| {
+        rows.iter().find(|(_, focusable, _)| {
+            focusable
+                .anchor
+                .is_some_and(|anchor| (point - anchor).abs().cmplt(Vec2::new(160., 18.)).all())
+        })
+    };
+
+    for tap in taps.read() {
+        if let Some((entity, ..)) = over(tap) {
+            writer.write(Activate(entity));
+        }
+    }
+
+    let (camera, gt) = camera.into_inner();
+    let Some((hovered, _, focused)) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world(gt, cursor).ok())
+        .map(|ray| ray.origin.truncate() / crate::RESOLUTION_SCALE)
+        .and_then(over)
+    else {
+        return;
+    };
+
+    // Only a moving cursor takes the focus, so a resting one doesn't fight the d-pad.
+    if !focused && moved {
+        for (entity, ..) in rows.iter().filter(|(.., focused)| *focused) {
+            commands.entity(entity).remove::<Focused>();
+        }
+        commands.entity(hovered).insert(Focused);
+    }
+
+    if input.just_pressed(MouseButton::Left) {
+        writer.write(Activate(hovered));
+    }
+}
+
+fn select(
+    mut commands: Commands,
+    mut activations: EventReader<Activate>,
+    rows: Query<&PauseRow>,
+    mut page: ResMut<PausePage>,
+    mut settings: ResMut<Persistent<Settings>>,
+    mut reset: ResMut<ResetTarget>,
+) {
+    for item in activations
+        .read()
+        .filter_map(|activate| rows.get(activate.0).ok())
+        .map(|row| row.0)
+    {
+        match item {
+            PauseItem::Resume => commands.set_state(PauseState::Running),
+            PauseItem::Settings => *page = PausePage::Settings,
+            PauseItem::Restart | PauseItem::Quit => {
+                *reset = if item == PauseItem::Restart {
+                    ResetTarget::Restart
+                } else {
+                    ResetTarget::Menu
+                };
+                commands.set_state(PauseState::Running);
+                commands.set_state(GameState::Reset);
+            }
+            PauseItem::Setting(setting) => {
+                setting.adjust(&mut settings, 0);
+                if let Err(e) = settings.persist() {
+                    error!("failed to save settings: {e}");
+                }
+            }
+            PauseItem::Back => *page = PausePage::Main,
+        }
+    }
+}
+
+fn update_rows(
+    settings: Res<Persistent<Settings>>,
+    mut rows: Query<(&PauseRow, &mut Text2d, &mut TextColor, Has<Focused>)>,
+) {
+    for (row, mut text, mut color, focused) in rows.iter_mut() {
+        text.0 = match row.0 {
+            PauseItem::Resume => String::from("RESUME"),
+            PauseItem::Settings => String::from("SETTINGS"),
+            PauseItem::Restart => String::from("RESTART RUN"),
+            PauseItem::Quit => String::from("QUIT TO MENU"),
+            PauseItem::Setting(setting) => {
+                format!("{}   {}", setting.label(), setting.value(&settings))
+            }
+            PauseItem::Back => String::from("BACK"),
+        };
+        color.0 = if focused {
+            POINT_COLOR.into()
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+fn back(
+    _: Trigger<Fired<Back>>,
+    mut commands: Commands,
+    pause: Res<State<PauseState>>,
+    mut page: ResMut<PausePage>,
+) {
+    if *pause.get() != PauseState::Paused {
+        return;
+    }
+
+    match *page {
+        PausePage::Main => commands.set_state(PauseState::Running),
+        PausePage::Settings => *page = PausePage::Main,
+    }
+}
+
+fn adjust_left(
+    _: Trigger<Fired<FocusLeft>>,
+    focused: Option<Single<&PauseRow, With<Focused>>>,
+    settings: ResMut<Persistent<Settings>>,
+) {
+    adjust(focused, settings, -1);
+}
+
+fn adjust_right(
+    _: Trigger<Fired<FocusRight>>,
+    focused: Option<Single<&PauseRow, With<Focused>>>,
+    settings: ResMut<Persistent<Settings>>,
+) {
+    adjust(focused, settings, 1);
+}
+
+fn adjust(
+    focused: Option<Single<&PauseRow, With<Focused>>>,
+    mut settings: ResMut<Persistent<Settings>>,
+    step: i32,
+) {
+    let Some(PauseItem::Setting(setting)) = focused.map(|row| row.0) else {
+        return;
+    };
+
+    setting.adjust(&mut settings, step);
+    if let Err(e) = settings.persist() {
+        error!("failed to save settings: {e}");
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
+use rand::Rng;
+
+use crate::RESOLUTION_SCALE;
+use crate::big::BigPoints;
+use crate::collectables::{
+    HexColor, MONEY_COLOR, MONEY_COLOR_REMOVE, MoneyEvent, POINT_COLOR, POINT_TEXT_Z, PointEvent,
+    SIZE,
+};
+use crate::text::TextFlash;
+
+pub struct PopupPlugin;
+
+impl Plugin for PopupPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Popups>()
+            .add_systems(PostUpdate, (show_popups, float_popups).chain());
+    }
+}
+
+/// Events closer than this, in world units, share a label.
+const CELL_SIZE: f32 = 48.;
+/// How long a label keeps taking in new events after it is shown or last grew.
+const MERGE_SECS: f32 = 0.2;
+const LIFE_SECS: f32 = 1.5;
+/// Labels on screen at once. Events past this still count, they just aren't shown.
+const MAX_LABELS: usize = 64;
+
+const DRIFT: f32 = 20.;
+const FLASH_SECS: f32 = 0.1;
+/// Growth for every doubling of the events merged into a label.
+const GROWTH: f32 = 0.15;
+const MAX_SCALE: f32 = 2.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum PopupKind {
+    Points,
+    Money,
+    MoneyLoss,
+}
+
+impl PopupKind {
+    fn color(&self) -> HexColor {
+        match self {
+            Self::Points => POINT_COLOR,
+            Self::Money => MONEY_COLOR,
+            Self::MoneyLoss => MONEY_COLOR_REMOVE,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Amount {
+    Points(BigPoints),
+    Money(i32),
+}
+
+impl Amount {
+    fn add(&mut self, other: Amount) {
+        match (self, other) {
+            (Self::Points(points), Self::Points(other)) => points.0 += other.0,
+            (Self::Money(money), Self::Money(other)) => *money += other,
+            _ => {}
+        }
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Self::Points(points) => format!("+{points}"),
+            Self::Money(money) => format!("${money}"),
+        }
+    }
+}
+
+/// A floating "+N" label, merging the events that land in its cell while it is open.
+#[derive(Component)]
+struct Popup {
+    key: (PopupKind, IVec2),
+    amount: Amount,
+    events: u32,
+    merge: Timer,
+    life: Timer,
+    drift: Vec2,
+}
+
+/// Open labels by kind and cell, and hidden labels ready to be reused.
+#[derive(Default, Resource)]
+struct Popups {
+    open: HashMap<(PopupKind, IVec2), Entity>,
+    free: Vec<Entity>,
+    shown: usize,
+}
+
+fn show_popups(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    mut popups: ResMut<Popups>,
+    mut points: EventReader<PointEvent>,
+    mut money: EventReader<MoneyEvent>,
+    mut labels: Query<(&mut Popup, &mut Text2d, &mut Transform)>,
+) {
+    let events = points
+        .read()
+        .map(|event| {
+            (
+                PopupKind::Points,
+                event.position,
+                Amount::Points(event.points.clone()),
+            )
+        })
+        .chain(money.read().map(|event| {
+            let kind = if event.money >= 0 {
+                PopupKind::Money
+            } else {
+                PopupKind::MoneyLoss
+            };
+            (kind, event.position, Amount::Money(event.money))
+        }));
+
+    // Merge this frame's events first: labels spawned below only exist from the next frame.
+    let mut merged = HashMap::<_, (Vec2, Amount, u32)>::new();
+    for (kind, position, amount) in events {
+        let key = (kind, (position / CELL_SIZE).floor().as_ivec2());
+        merged
+            .entry(key)
+            .and_modify(|(_, total, events)| {
+                total.add(amount.clone());
+                *events += 1;
+            })
+            .or_insert((position, amount, 1));
+    }
+
+    let mut rng = rand::thread_rng();
+    for (key, (position, amount, events)) in merged {
+        let kind = key.0;
+
+        if let Some((mut popup, mut text, mut transform)) = popups
+            .open
+            .get(&key)
+            .and_then(|entity| labels.get_mut(*entity).ok())
+        {
+            popup.amount.add(amount);
+            popup.events += events;
+            popup.merge.reset();
+            popup.life.reset();
+            text.0 = popup.amount.text();
+            transform.scale = Vec3::splat(scale(popup.events));
+            continue;
+        }
+
+        if popups.shown >= MAX_LABELS {
+            continue;
+        }
+        popups.shown += 1;
+
+        let rotation = rng.gen_range(-PI / 9.0..PI / 9.0);
+        let drift = Vec2::from_angle(rng.gen_range(-PI..PI)) * DRIFT;
+        let label = (
+            Popup {
+                key,
+                amount: amount.clone(),
+                events,
+                merge: Timer::from_seconds(MERGE_SECS, TimerMode::Once),
+                life: Timer::from_seconds(LIFE_SECS, TimerMode::Once),
+                drift,
+            },
+            Text2d::new(amount.text()),
+            TextFlash::new(FLASH_SECS, kind.color(), Color::WHITE),
+            TextColor(kind.color().into()),
+            Transform::from_translation((position * RESOLUTION_SCALE).extend(POINT_TEXT_Z))
+                .with_rotation(Quat::from_rotation_z(rotation))
+                .with_scale(Vec3::splat(scale(events))),
+            Visibility::Inherited,
+        );
+
+        let entity = match popups.free.pop() {
+            Some(entity) => {
+                commands.entity(entity).insert(label);
+                entity
+            }
+            None => commands
+                .spawn((
+                    HIGH_RES_LAYER,
+                    TextFont {
+                        font: server.load("fonts/cube.ttf"),
+                        font_size: SIZE,
+                        ..Default::default()
+                    },
+                    label,
+                ))
+                .id(),
+        };
+        popups.open.insert(key, entity);
+    }
+}
+
+fn scale(events: u32) -> f32 {
+    (1. + (events as f32).log2() * GROWTH).min(MAX_SCALE)
+}
+
+/// Drifts and fades labels, closing them to new events and hiding them for reuse.
+fn float_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut popups: ResMut<Popups>,
+    mut labels: Query<(
+        Entity,
+        &mut Popup,
+        &mut Transform,
+        &mut TextColor,
+        &mut Visibility,
+    )>,
+) {
+    for (entity, mut popup, mut transform, mut color, mut visibility) in labels.iter_mut() {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+
+        if popup.merge.tick(time.delta()).just_finished()
+            && popups.open.get(&popup.key) == Some(&entity)
+        {
+            popups.open.remove(&popup.key);
+        }
+
+        if popup.life.tick(time.delta()).finished() {
+            *visibility = Visibility::Hidden;
+            commands.entity(entity).remove::<TextFlash>();
+            popups.shown -= 1;
+            popups.free.push(entity);
+            continue;
+        }
+
+        let t = popup.life.fraction();
+        let ease = 1. - t.powi(4);
+        transform.translation += (popup.drift * ease * time.delta_secs()).extend(0.);
+        color.0.set_alpha(ease);
+    }
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use rand::RngCore;
+
+use crate::state::StateAppExt;
+
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(RunRng::from_entropy())
+            .add_reset(|mut rng: ResMut<RunRng>| {
+                *rng = RunRng::from_entropy();
+            });
+    }
+}
+
+/// Deterministic random source for everything that affects a run.
+///
+/// Cosmetic randomness (text rotation, particle jitter) can keep using `thread_rng`.
+#[derive(Debug, Clone, Resource, serde::Serialize, serde::Deserialize)]
+pub struct RunRng {
+    seed: u64,
+    state: u64,
+}
+
+impl RunRng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    pub fn from_entropy() -> Self {
+        Self::new(rand::random())
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl RngCore for RunRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    // splitmix64
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
@@ -1,11 +1,13 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
+use bevy_enhanced_input::events::Fired;
 use bevy_optix::pixel_perfect::{HIGH_RES_LAYER, OuterCamera};
 
 use crate::ball::PlayerBall;
 use crate::feature::{FeatureSpawner, spawn_feature_list};
+use crate::input::SandboxBall;
 use crate::selection::{SelectedFeature, SelectionFeature};
-use crate::state::{GameState, Playing};
+use crate::state::GameState;
 
 pub const ENABLED: bool = false;
 
@@ -26,7 +28,7 @@ impl Plugin for SandboxPlugin {
                     OnEnter(GameState::StartGame),
                     spawn_selection.after(spawn_feature_list),
                 )
-                .add_systems(Update, spawn_ball.in_set(Playing));
+                .add_observer(spawn_ball);
         }
     }
 }
@@ -36,20 +38,18 @@ fn move_camera(mut camera: Single<&mut Transform, With<OuterCamera>>) {
 }
 
 fn spawn_ball(
+    _: Trigger<Fired<SandboxBall>>,
     mut commands: Commands,
-    input: Res<ButtonInput<MouseButton>>,
+    state: Res<State<GameState>>,
     window: Single<&Window, With<PrimaryWindow>>,
     camera: Single<(&Camera, &GlobalTransform), With<OuterCamera>>,
     selection_entity: Option<Single<&SelectedFeature>>,
 ) {
-    if selection_entity.is_some() {
+    if selection_entity.is_some() || *state.get() != GameState::Playing {
         return;
     }
 
     let (camera, gt) = camera.into_inner();
-    if !input.just_pressed(MouseButton::Right) {
-        return;
-    }
 
     let Some(world_position) = window
         .cursor_position()
@@ -0,0 +1,248 @@
+use bincode::Options;
+use serde::de::DeserializeOwned;
+
+use crate::big::BigPoints;
+
+use super::{MAGIC, PlayerData, VERSION};
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Bincode(bincode::Error),
+    UnknownVersion(u32),
+}
+
+impl core::fmt::Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Bincode(e) => write!(f, "{e}"),
+            Self::UnknownVersion(version) => write!(f, "unknown save version v{version}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for SaveError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<bincode::Error> for SaveError {
+    fn from(value: bincode::Error) -> Self {
+        Self::Bincode(value)
+    }
+}
+
+type Migration = fn(&[u8]) -> Result<Vec<u8>, SaveError>;
+
+/// `MIGRATIONS[n]` converts a v`n` payload into a v`n + 1` payload.
+const MIGRATIONS: [Migration; VERSION as usize] = [v0_to_v1, v1_to_v2, v2_to_v3];
+
+/// Decodes a save of any known version and upgrades it to the current [`PlayerData`].
+///
+/// Returns the version the save was written with.
+pub fn upgrade(bytes: &[u8]) -> Result<(u32, PlayerData), SaveError> {
+    let (from, payload) = split_version(bytes)?;
+
+    let mut payload = payload.to_vec();
+    for step in MIGRATIONS
+        .get(from as usize..)
+        .ok_or(SaveError::UnknownVersion(from))?
+    {
+        payload = step(&payload)?;
+    }
+
+    Ok((from, decode(&payload)?))
+}
+
+/// Splits a save into its version and payload.
+///
+/// Only the magic header tells an enveloped save from one written before the envelope.
+fn split_version(bytes: &[u8]) -> Result<(u32, &[u8]), SaveError> {
+    let Some(rest) = bytes.strip_prefix(&MAGIC) else {
+        return pre_envelope_version(bytes).map(|version| (version, bytes));
+    };
+
+    let version: u32 = bincode::deserialize(rest)?;
+    Ok((version, &rest[size_of::<u32>()..]))
+}
+
+/// Saves written before the envelope, v0 and v1, carry no version. Their schemas
+/// don't overlap, so whichever decodes exactly is the right one.
+fn pre_envelope_version(bytes: &[u8]) -> Result<u32, SaveError> {
+    if decode::<V1PlayerData>(bytes).is_ok() {
+        return Ok(1);
+    }
+
+    decode::<V0PlayerData>(bytes)?;
+    Ok(0)
+}
+
+/// Same encoding as [`bincode::deserialize`], but trailing bytes are an error.
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, SaveError> {
+    Ok(bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+        .deserialize(bytes)?)
+}
+
+// The schemas below are frozen copies of what was written to disk. Never edit them.
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct V0PlayerData {
+    /// (level, points)
+    point_record: Vec<(usize, BigPoints)>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct V1PlayerData {
+    point_record: Vec<V1Entry>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct V1Entry {
+    initials: String,
+    timestamp: u64,
+    seed: u64,
+    level: usize,
+    points: BigPoints,
+    features: Vec<String>,
+    mode: V1GameMode,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+enum V1GameMode {
+    Standard,
+    #[allow(unused)]
+    Sandbox,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct V3PlayerData {
+    point_record: Vec<V1Entry>,
+    /// Variant indices of unlocked achievements.
+    achievements: Vec<u32>,
+}
+
+/// v1 gave every score initials and run metadata.
+fn v0_to_v1(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let old: V0PlayerData = decode(payload)?;
+    let new = V1PlayerData {
+        point_record: old
+            .point_record
+            .into_iter()
+            .map(|(level, points)| V1Entry {
+                initials: String::from("---"),
+                timestamp: 0,
+                seed: 0,
+                level,
+                points,
+                features: Vec::new(),
+                mode: V1GameMode::Standard,
+            })
+            .collect(),
+    };
+
+    Ok(bincode::serialize(&new)?)
+}
+
+/// v2 only introduced the [`SaveEnvelope`](super::SaveEnvelope), the payload is unchanged.
+fn v1_to_v2(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    decode::<V1PlayerData>(payload)?;
+    Ok(payload.to_vec())
+}
+
+/// v3 added achievements, which start out locked.
+fn v2_to_v3(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let old: V1PlayerData = decode(payload)?;
+    let new = V3PlayerData {
+        point_record: old.point_record,
+        achievements: Vec::new(),
+    };
+
+    Ok(bincode::serialize(&new)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::SaveEnvelope;
+
+    #[test]
+    fn upgrade_v0() {
+        let (from, data) = upgrade(include_bytes!("fixtures/v0.bin")).unwrap();
+        assert_eq!(from, 0);
+
+        let levels = data
+            .point_record
+            .iter()
+            .map(|entry| (entry.level, entry.points.0.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            levels,
+            [
+                (0, String::from("20")),
+                (2, String::from("1250")),
+                (1, String::from("123456789012345678901234567890")),
+            ]
+        );
+        assert!(
+            data.point_record
+                .iter()
+                .all(|entry| entry.initials == "---")
+        );
+    }
+
+    #[test]
+    fn upgrade_v1() {
+        let (from, data) = upgrade(include_bytes!("fixtures/v1.bin")).unwrap();
+        assert_eq!(from, 1);
+        assert_eq!(data.point_record.len(), 2);
+
+        let first = &data.point_record[0];
+        assert_eq!(first.initials, "ABC");
+        assert_eq!(first.timestamp, 1_750_000_000);
+        assert_eq!(first.seed, 42);
+        assert_eq!(first.level, 3);
+        assert_eq!(first.points.0.to_string(), "98765");
+        assert_eq!(first.features, ["Bumper", "Dispenser"]);
+        assert!(data.achievements.is_empty());
+    }
+
+    #[test]
+    fn upgrade_v2() {
+        let (from, data) = upgrade(include_bytes!("fixtures/v2.bin")).unwrap();
+        assert_eq!(from, 2);
+        assert_eq!(data.point_record.len(), 2);
+
+        let first = &data.point_record[0];
+        assert_eq!(first.initials, "ABC");
+        assert_eq!(first.points.0.to_string(), "98765");
+        assert_eq!(first.features, ["Bumper", "Dispenser"]);
+        assert!(data.achievements.is_empty());
+    }
+
+    #[test]
+    fn round_trip_current() {
+        let (_, data) = upgrade(include_bytes!("fixtures/v1.bin")).unwrap();
+        let bytes = bincode::serialize(&SaveEnvelope::new(data)).unwrap();
+
+        let (from, data) = upgrade(&bytes).unwrap();
+        assert_eq!(from, VERSION);
+        assert_eq!(data.point_record.len(), 2);
+        assert_eq!(bincode::serialize(&SaveEnvelope::new(data)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn reject_garbage() {
+        assert!(upgrade(b"not a save file").is_err());
+
+        let mut future = MAGIC.to_vec();
+        future.extend_from_slice(&(VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            upgrade(&future),
+            Err(SaveError::UnknownVersion(version)) if version == VERSION + 1
+        ));
+    }
+}
@@ -0,0 +1,250 @@
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy_persistent::prelude::*;
+
+use crate::achievements::Achievement;
+use crate::leaderboard::LeaderboardEntry;
+
+mod migrations;
+mod run;
+
+pub use migrations::SaveError;
+pub use run::{ResumeRun, RunSave};
+
+/// Bumped whenever [`PlayerData`] changes shape. Each bump needs a step in
+/// [`migrations::MIGRATIONS`].
+pub const VERSION: u32 = 3;
+
+const MAGIC: [u8; 4] = *b"SLUG";
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(run::RunSavePlugin)
+            .add_systems(PreStartup, player_data);
+    }
+}
+
+pub type PlayerSave = Persistent<SaveEnvelope<PlayerData>>;
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+pub struct PlayerData {
+    /// Sorted from highest to lowest score.
+    pub point_record: Vec<LeaderboardEntry>,
+    /// In the order they were unlocked.
+    pub achievements: Vec<Achievement>,
+}
+
+/// Prefixes saved data with a magic number and schema version so that old saves
+/// can be recognised and migrated instead of discarded.
+#[derive(Resource, serde::Serialize, serde::Deserialize)]
+pub struct SaveEnvelope<T> {
+    magic: [u8; 4],
+    version: u32,
+    data: T,
+}
+
+impl<T> SaveEnvelope<T> {
+    pub fn new(data: T) -> Self {
+        Self {
+            magic: MAGIC,
+            version: VERSION,
+            data,
+        }
+    }
+}
+
+impl<T: Default> Default for SaveEnvelope<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> Deref for SaveEnvelope<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<T> DerefMut for SaveEnvelope<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+pub fn save_dir() -> PathBuf {
+    let element = "slugball-player-data";
+    dirs::config_dir()
+        .map(|native_config_dir| native_config_dir.join(element))
+        .unwrap_or_else(|| Path::new("local").join(element))
+}
+
+fn player_data(mut commands: Commands) {
+    let path = save_dir().join("data");
+
+    #[cfg(not(target_arch = "wasm32"))]
+    migrate(&path);
+
+    let build = |path: &Path, revert: bool| {
+        PlayerSave::builder()
+            .name("player data")
+            .format(StorageFormat::Bincode)
+            .path(path)
+            .default(Default::default())
+            .revertible(true)
+            .revert_to_default_on_deserialization_errors(revert)
+            .build()
+    };
+
+    // Web storage can't be migrated ahead of time, so unreadable web saves are only
+    // caught here.
+    let save = build(&path, false).or_else(|e| {
+        error!("failed to load player data: {e}");
+        quarantine(&path);
+        build(&path, true)
+    });
+    commands.insert_resource(or_scratch(save, "data", "player data", |path| {
+        build(path, true)
+    }));
+}
+
+/// Falls back from a save that couldn't be built in [`save_dir`] to `file` in the scratch
+/// directory, and from there to [`nowhere`], so an unusable save directory costs the
+/// player their progress for the session rather than the game.
+pub fn or_scratch<R: Resource + serde::Serialize + serde::de::DeserializeOwned>(
+    save: Result<Persistent<R>, PersistenceError>,
+    file: &str,
+    name: &str,
+    build: impl Fn(&Path) -> Result<Persistent<R>, PersistenceError>,
+) -> Persistent<R> {
+    save.or_else(|e| {
+        error!("failed to start new {name}, it won't be kept: {e}");
+        build(&scratch_dir().join(file))
+    })
+    .or_else(|e| {
+        error!("failed to keep {name} for the session: {e}");
+        build(&nowhere(file))
+    })
+    .unwrap_or_else(|e| panic!("{name} can't even be held in memory: {e}"))
+}
+
+/// Somewhere to keep saves for the session when the save directory is unusable.
+fn scratch_dir() -> PathBuf {
+    let root = if cfg!(target_arch = "wasm32") {
+        PathBuf::from("session")
+    } else {
+        std::env::temp_dir()
+    };
+    root.join("slugball-player-data")
+}
+
+/// Storage that takes every write and keeps nothing. Reading it fails to deserialize, so
+/// a save built here with errors reverting to default just lives in memory.
+fn nowhere(file: &str) -> PathBuf {
+    if cfg!(target_arch = "wasm32") {
+        // Web storage has no such sink, a second session key is the closest thing.
+        scratch_dir().join("discard").join(file)
+    } else if cfg!(windows) {
+        PathBuf::from("NUL")
+    } else {
+        PathBuf::from("/dev/null")
+    }
+}
+
+/// Upgrades the save at `path` to [`VERSION`] in place.
+///
+/// The original file is backed up before it is rewritten. Saves that can't be
+/// read are moved aside so that [`Persistent`] starts fresh without destroying them.
+#[cfg(not(target_arch = "wasm32"))]
+fn migrate(path: &Path) {
+    let Ok(bytes) = std::fs::read(path) else {
+        return;
+    };
+
+    match migrations::upgrade(&bytes) {
+        Ok((from, _)) if from == VERSION => {}
+        Ok((from, data)) => {
+            info!("migrating player data from v{from} to v{VERSION}");
+            if let Err(e) = std::fs::copy(path, sibling(path, &format!("v{from}-backup"))) {
+                error!("failed to back up player data, leaving it untouched: {e}");
+                return;
+            }
+
+            let result = bincode::serialize(&SaveEnvelope::new(data))
+                .map_err(SaveError::from)
+                .and_then(|bytes| std::fs::write(path, bytes).map_err(SaveError::from));
+            if let Err(e) = result {
+                error!("failed to write migrated player data: {e}");
+                quarantine(path);
+            }
+        }
+        Err(e) => {
+            error!("failed to read player data: {e}");
+            quarantine(path);
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn quarantine(path: &Path) {
+    let destination = sibling(path, "corrupt");
+    match std::fs::rename(path, &destination) {
+        Ok(()) => warn!("moved unreadable player data to {}", destination.display()),
+        Err(e) => error!("failed to quarantine player data: {e}"),
+    }
+}
+
+/// Moves the stored save aside under a `corrupt` key, as native saves are moved aside
+/// on disk.
+#[cfg(target_arch = "wasm32")]
+fn quarantine(path: &Path) {
+    let Some(storage) = web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+    else {
+        error!("failed to quarantine player data: no local storage");
+        return;
+    };
+
+    // Persistent keys local storage by what follows the `local` prefix of the path.
+    let name = path.strip_prefix("local").unwrap_or(path).to_string_lossy();
+    let key = (0..storage.length().unwrap_or_default())
+        .filter_map(|index| storage.key(index).ok().flatten())
+        .find(|key| key.trim_start_matches('/') == name.trim_start_matches('/'));
+    let Some((key, value)) = key.and_then(|key| {
+        storage
+            .get_item(&key)
+            .ok()
+            .flatten()
+            .map(|value| (key, value))
+    }) else {
+        return;
+    };
+
+    let destination = format!("{key}.corrupt.{}", (js_sys::Date::now() / 1000.) as u64);
+    match storage
+        .set_item(&destination, &value)
+        .and_then(|()| storage.remove_item(&key))
+    {
+        Ok(()) => warn!("moved unreadable player data to {destination}"),
+        Err(e) => error!("failed to quarantine player data: {e:?}"),
+    }
+}
+
+/// `path` with a tag and timestamp appended, e.g. `data.v0-backup.1718000000`.
+#[cfg(not(target_arch = "wasm32"))]
+fn sibling(path: &Path, tag: &str) -> PathBuf {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    path.with_file_name(format!("{name}.{tag}.{timestamp}"))
+}
@@ -0,0 +1,173 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy_persistent::prelude::*;
+
+use crate::big::BigPoints;
+use crate::collectables::{Money, Points, TotalPoints};
+use crate::feature::grid::{FeatureSlot, SlotFeatureOf};
+use crate::feature::{Bonks, FeatureSpawner};
+use crate::rng::RunRng;
+use crate::selection::{FeaturePack, FeaturePacks, SelectionEvent, SelectionFeature};
+use crate::stage::Stage;
+use crate::state::{self, GameState, ResetTarget, StateAppExt};
+use crate::tooltips::Tooltips;
+
+pub struct RunSavePlugin;
+
+impl Plugin for RunSavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreStartup, run_save)
+            .add_systems(OnEnter(GameState::Selection), snapshot_run)
+            .add_systems(
+                Update,
+                restore_run
+                    .run_if(resource_exists::<ResumeRun>)
+                    .in_set(state::StartGame),
+            )
+            .add_reset(clear_run);
+    }
+}
+
+/// The last [`RunSnapshot`], if the run it belongs to hasn't ended yet.
+#[derive(Default, Resource, serde::Serialize, serde::Deserialize)]
+pub struct RunSave(Option<RunSnapshot>);
+
+impl RunSave {
+    pub fn snapshot(&self) -> Option<&RunSnapshot> {
+        self.0.as_ref()
+    }
+}
+
+/// Everything needed to rebuild a run at the start of a [`GameState::Selection`].
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct RunSnapshot {
+    stage: Stage,
+    money: i32,
+    points: BigPoints,
+    total_points: BigPoints,
+    features: Vec<PlacedFeature>,
+    packs: Vec<FeaturePack>,
+    rng: RunRng,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct PlacedFeature {
+    /// Index of the [`FeatureSlot`] the feature occupies.
+    slot: usize,
+    /// [`Tooltips::name`] of the feature.
+    name: String,
+    bonks: Bonks,
+}
+
+/// Inserted when the player continues a run. Consumed once the run is rebuilt.
+#[derive(Resource)]
+pub struct ResumeRun(pub RunSnapshot);
+
+fn run_save(mut commands: Commands) {
+    let build = |path: &Path| {
+        Persistent::<RunSave>::builder()
+            .name("run")
+            .format(StorageFormat::Bincode)
+            .path(path)
+            .default(Default::default())
+            // A stale snapshot is not worth keeping around like player data is.
+            .revert_to_default_on_deserialization_errors(true)
+            .build()
+    };
+
+    let save = build(&super::save_dir().join("run"));
+    commands.insert_resource(super::or_scratch(save, "run", "run", build));
+}
+
+fn snapshot_run(
+    stage: Single<&Stage>,
+    packs: Single<&FeaturePacks>,
+    money: Res<Money>,
+    points: Res<Points>,
+    total_points: Res<TotalPoints>,
+    rng: Res<RunRng>,
+    features: Query<(&SlotFeatureOf, &Tooltips, &Bonks)>,
+    slots: Query<&FeatureSlot>,
+    mut save: ResMut<Persistent<RunSave>>,
+) {
+    let features = features
+        .iter()
+        .filter_map(|(slot, tips, bonks)| {
+            Some(PlacedFeature {
+                slot: slots.get(slot.0).ok()?.0,
+                name: tips.name.to_string(),
+                bonks: bonks.clone(),
+            })
+        })
+        .collect();
+
+    save.0 = Some(RunSnapshot {
+        stage: stage.clone(),
+        money: money.get(),
+        points: points.get().clone(),
+        total_points: total_points.get().clone(),
+        features,
+        packs: packs.packs().to_vec(),
+        rng: rng.clone(),
+    });
+
+    if let Err(e) = save.persist() {
+        error!("failed to save run: {e}");
+    }
+}
+
+fn restore_run(
+    mut commands: Commands,
+    resume: Res<ResumeRun>,
+    mut stage: Single<&mut Stage>,
+    slots: Query<(Entity, &FeatureSlot)>,
+    spawners: Query<
+        (&Tooltips, &FeatureSpawner),
+        (Without<SlotFeatureOf>, Without<SelectionFeature>),
+    >,
+    mut writer: EventWriter<SelectionEvent>,
+) {
+    // The grid spawns its slots a frame after the feature zone.
+    if slots.is_empty() {
+        return;
+    }
+
+    let snapshot = &resume.0;
+    **stage = snapshot.stage.clone();
+    commands.insert_resource(Money::new(snapshot.money));
+    commands.insert_resource(Points::new(snapshot.points.clone()));
+    commands.insert_resource(TotalPoints::new(snapshot.total_points.clone()));
+    commands.insert_resource(snapshot.rng.clone());
+
+    for placed in snapshot.features.iter() {
+        let Some((slot, _)) = slots.iter().find(|(_, slot)| slot.0 == placed.slot) else {
+            warn!("no slot {} for saved `{}`", placed.slot, placed.name);
+            continue;
+        };
+        let Some((_, spawner)) = spawners.iter().find(|(tips, _)| tips.name == placed.name) else {
+            warn!("unknown saved feature `{}`", placed.name);
+            continue;
+        };
+
+        let mut entity = commands.spawn((SlotFeatureOf(slot), ChildOf(slot), Transform::default()));
+        spawner.0(&mut entity);
+        entity.insert(placed.bonks.clone());
+    }
+
+    writer.write(SelectionEvent {
+        packs: snapshot.packs.clone(),
+    });
+    commands.remove_resource::<ResumeRun>();
+}
+
+fn clear_run(mut save: ResMut<Persistent<RunSave>>, target: Res<ResetTarget>) {
+    if *target == ResetTarget::Menu {
+        return;
+    }
+
+    save.0 = None;
+    if let Err(e) = save.persist() {
+        error!("failed to clear saved run: {e}");
+    }
+}
@@ -1,4 +1,3 @@
-use std::ops::Deref;
 use std::time::Duration;
 
 use avian2d::prelude::ColliderDisabled;
@@ -6,15 +5,18 @@ use bevy::ecs::entity_disabling::Disabled;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use bevy_optix::pixel_perfect::{HIGH_RES_LAYER, OuterCamera};
-use bevy_seedling::sample::SamplePlayer;
 
-use crate::collectables::{Money, MoneyEvent};
+use crate::audio::PlaySound;
+use crate::collectables::{Money, MoneyEvent, POINT_COLOR};
 use crate::feature::grid::{FeatureSlot, SlotFeature, SlotFeatureOf};
 use crate::feature::{FeatureSpawner, Price, Rarity};
+use crate::focus::{Activate, Focusable, Focused};
+use crate::rng::RunRng;
 use crate::sandbox;
 use crate::stage::{AdvanceEvent, StageSet};
 use crate::state::{GameState, Playing, StateAppExt, remove_entities};
-use crate::tooltips::{Hover, ShowTooltips};
+use crate::tooltips::ShowTooltips;
+use crate::touch::Taps;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
 pub struct SelectionSet;
@@ -33,23 +35,29 @@ impl Plugin for SelectionPlugin {
                     .after(StageSet)
                     .in_set(SelectionSet),
             )
-            .add_systems(Update, (handle_delayed, button_system))
+            .add_systems(
+                Update,
+                (
+                    handle_delayed,
+                    button_system,
+                    activate_selection,
+                    focus_slots,
+                ),
+            )
+            .add_systems(OnEnter(GameState::Selection), begin_selection)
             .add_systems(OnEnter(SelectionState::SpawnSelection), spawn_selection);
         //.add_systems(Update, report_entities);
 
         if sandbox::ENABLED {
-            app.add_systems(
-                Update,
-                (spawn_feature, select_feature).chain().in_set(Playing),
-            );
+            app.add_systems(Update, spawn_feature.in_set(Playing));
         } else {
             app.add_systems(
                 Update,
-                (spawn_feature, select_feature)
-                    .chain()
-                    .run_if(in_state(SelectionState::SelectAndSpawn)),
+                spawn_feature.run_if(in_state(SelectionState::SelectAndSpawn)),
             );
         }
+
+        app.add_observer(select_feature);
     }
 }
 
@@ -65,7 +73,7 @@ pub struct SelectionEvent {
     pub packs: Vec<FeaturePack>,
 }
 
-#[derive(Clone, Eq, PartialEq, Debug, Hash, Component)]
+#[derive(Clone, Eq, PartialEq, Debug, Hash, Component, serde::Serialize, serde::Deserialize)]
 pub enum FeaturePack {
     Starter,
 }
@@ -76,8 +84,21 @@ impl FeaturePack {
     }
 }
 
+/// Packs left to open in the current selection, including the one on screen.
 #[derive(Component)]
-struct FeaturePacks(Vec<FeaturePack>);
+pub struct FeaturePacks(Vec<FeaturePack>);
+
+impl FeaturePacks {
+    pub fn packs(&self) -> &[FeaturePack] {
+        &self.0
+    }
+
+    /// Discards the pack on screen and returns whether any remain.
+    fn finish_pack(&mut self) -> bool {
+        self.0.pop();
+        !self.0.is_empty()
+    }
+}
 
 fn receive_advance(mut reader: EventReader<AdvanceEvent>, mut writer: EventWriter<SelectionEvent>) {
     for event in reader.read() {
@@ -95,11 +116,14 @@ fn enter(mut commands: Commands, mut reader: EventReader<SelectionEvent>) {
     for event in reader.read() {
         debug_assert!(!event.packs.is_empty(), "selection needs atleast 1 pack");
         commands.set_state(GameState::Selection);
-        commands.set_state(SelectionState::SpawnSelection);
         commands.spawn(FeaturePacks(event.packs.clone()));
     }
 }
 
+fn begin_selection(mut commands: Commands) {
+    commands.set_state(SelectionState::SpawnSelection);
+}
+
 #[derive(Component)]
 struct Selection;
 
@@ -122,14 +146,13 @@ const SELECTIONZ: f32 = 800.;
 
 fn spawn_selection(
     mut commands: Commands,
-    mut packs: Single<&mut FeaturePacks>,
+    packs: Single<&FeaturePacks>,
     features: Query<(&Rarity, &FeatureSpawner)>,
+    mut rng: ResMut<RunRng>,
 
     mut rare_offset: Local<f32>,
 ) {
-    let mut rng = rand::thread_rng();
-
-    let Some(pack) = packs.0.pop() else {
+    let Some(pack) = packs.0.last() else {
         debug_assert!(false, "`FeaturePacks` has 0 packs");
         return;
     };
@@ -151,7 +174,7 @@ fn spawn_selection(
     let mut sampler = crate::sampler::Sampler::new(&samples);
 
     let features = match pack {
-        FeaturePack::Starter => sampler.sample_unique(&mut rng, 3),
+        FeaturePack::Starter => sampler.sample_unique(&mut *rng, 3),
     };
 
     let positions = [-300., 0., 300.];
@@ -163,6 +186,7 @@ fn spawn_selection(
             spawner.clone(),
             Selection,
             SelectionFeature,
+            Focusable::default(),
             ColliderDisabled,
             Transform::from_xyz(x, y, SELECTIONZ),
         ));
@@ -212,6 +236,9 @@ fn handle_delayed(
 #[derive(Debug, Component)]
 struct SkipButton;
 
+/// Where the skip button sits in world space, below the offers.
+const SKIP_POSITION: Vec2 = Vec2::new(0., -270.);
+
 fn button() -> impl Bundle {
     (
         HIGH_RES_LAYER,
@@ -229,6 +256,7 @@ fn button() -> impl Bundle {
             HIGH_RES_LAYER,
             SkipButton,
             Button,
+            Focusable::at(SKIP_POSITION),
             Node {
                 width: Val::Px(150.0),
                 height: Val::Px(65.0),
@@ -259,41 +287,76 @@ fn button() -> impl Bundle {
     )
 }
 
+/// Clicking or tapping an offer's card selects it.
 fn select_feature(
+    trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
-    options: Query<
-        (Entity, &FeatureSpawner, &GlobalTransform, &Price),
-        //(With<SelectionFeature>, With<Feature>),
-    >,
+    options: Query<Entity, With<SelectionFeature>>,
     child_ofs: Query<&ChildOf>,
-    input: Res<ButtonInput<MouseButton>>,
-    window: Single<&Window, With<PrimaryWindow>>,
-    camera: Single<(&Camera, &GlobalTransform), With<OuterCamera>>,
+    cards: Query<&ChildOf, With<Sprite>>,
+) {
+    if trigger.button != PointerButton::Primary {
+        return;
+    }
+
+    let Ok(card) = cards.get(trigger.target()) else {
+        return;
+    };
+
+    let Some(option) = options.iter().find(|entity| {
+        child_ofs
+            .get(card.parent())
+            .is_ok_and(|child_of| child_of.parent() == *entity)
+    }) else {
+        return;
+    };
+
+    commands.run_system_cached_with(buy_offer, option);
+}
+
+/// Confirming the focused offer or skip button does the same as clicking it.
+fn activate_selection(
+    mut commands: Commands,
+    mut activations: EventReader<Activate>,
+    options: Query<(), With<SelectionFeature>>,
+    skip: Query<(), (With<SkipButton>, With<Button>)>,
+) {
+    for activate in activations.read() {
+        if options.contains(activate.0) {
+            commands.run_system_cached_with(buy_offer, activate.0);
+        } else if skip.contains(activate.0) {
+            commands.run_system_cached(skip_pack);
+        }
+    }
+}
+
+fn buy_offer(
+    option: In<Entity>,
+    mut commands: Commands,
+    options: Query<(&FeatureSpawner, &GlobalTransform, &Price), With<SelectionFeature>>,
     selection_entities: Query<Entity, With<Selection>>,
-    hovered: Single<&ChildOf, With<Hover>>,
+    selection_state: Res<State<SelectionState>>,
+    game_state: Res<State<GameState>>,
     money: Res<Money>,
     mut money_event: EventWriter<MoneyEvent>,
-    server: Res<AssetServer>,
+    mut sounds: EventWriter<PlaySound>,
     skip: Query<Entity, With<SkipButton>>,
 ) {
-    let (camera, gt) = camera.into_inner();
-    if !input.just_pressed(MouseButton::Left) {
+    let selecting = if sandbox::ENABLED {
+        *game_state.get() == GameState::Playing
+    } else {
+        *selection_state.get() == SelectionState::SelectAndSpawn
+    };
+    if !selecting {
         return;
     }
 
-    let Some((_, selected_feature, transform, price)) = options.iter().find(|(entity, ..)| {
-        child_ofs
-            .get(hovered.parent())
-            .is_ok_and(|child_of| child_of.parent() == *entity)
-    }) else {
+    let Ok((selected_feature, transform, price)) = options.get(*option) else {
         return;
     };
 
     if price.0 > money.get() {
-        commands.spawn(
-            SamplePlayer::new(server.load("audio/pinball/1drop.ogg"))
-                .with_volume(bevy_seedling::prelude::Volume::Decibels(-12.0)),
-        );
+        sounds.write(PlaySound::new("shop.deny"));
         return;
     }
 
@@ -302,33 +365,6 @@ fn select_feature(
         position: transform.translation().xy(),
     });
 
-    //let Some(world_position) = window
-    //    .cursor_position()
-    //    .and_then(|cursor| camera.viewport_to_world(gt, cursor).ok())
-    //    .map(|ray| ray.origin.truncate() / crate::RESOLUTION_SCALE)
-    //else {
-    //    return;
-    //};
-    //
-    //let Some((_, selected_feature, transform)) = options.iter().min_by(|a, b| {
-    //    let a = world_position.distance_squared(a.2.translation().xy());
-    //    let b = world_position.distance_squared(b.2.translation().xy());
-    //
-    //    a.total_cmp(&b)
-    //}) else {
-    //    return;
-    //};
-    //
-    //if transform
-    //    .compute_transform()
-    //    .translation
-    //    .xy()
-    //    .distance(world_position)
-    //    > 50.0
-    //{
-    //    return;
-    //}
-
     commands.spawn(SelectedFeature(selected_feature.clone()));
     if !sandbox::ENABLED {
         for entity in selection_entities.iter() {
@@ -343,40 +379,22 @@ fn select_feature(
 }
 
 fn button_system(
-    mut interaction_query: Query<
-        (Entity, &Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<SkipButton>),
-    >,
-    selection_entities: Query<Entity, With<Selection>>,
     mut commands: Commands,
-    packs: Option<Single<(Entity, &FeaturePacks)>>,
+    mut buttons: Query<(Ref<Interaction>, Has<Focused>, &mut BackgroundColor), With<SkipButton>>,
 ) {
-    for (entity, interaction, mut color) in &mut interaction_query {
-        info!("here");
+    for (interaction, focused, mut color) in &mut buttons {
         match *interaction {
             Interaction::Pressed => {
-                if !sandbox::ENABLED {
-                    for entity in selection_entities.iter() {
-                        commands.entity(entity).despawn();
-                        //.insert_recursive::<Children>(Disabled);
-                    }
-
-                    commands.entity(entity).despawn();
-
-                    if let Some(packs) = &packs {
-                        let (entity, packs) = packs.deref();
-                        if packs.0.is_empty() {
-                            commands.set_state(GameState::Playing);
-                            commands.entity(*entity).despawn();
-                        } else {
-                            commands.set_state(SelectionState::SpawnSelection);
-                        }
-                    }
+                if interaction.is_changed() {
+                    commands.run_system_cached(skip_pack);
                 }
             }
             Interaction::Hovered => {
                 *color = Color::srgb(0.4, 0.4, 0.4).into();
             }
+            Interaction::None if focused => {
+                *color = Color::srgb(0.4, 0.4, 0.4).into();
+            }
             Interaction::None => {
                 *color = Color::srgb(0.2, 0.2, 0.2).into();
             }
@@ -384,6 +402,74 @@ fn button_system(
     }
 }
 
+fn skip_pack(
+    mut commands: Commands,
+    selection_entities: Query<Entity, With<Selection>>,
+    skip: Query<Entity, With<SkipButton>>,
+    packs: Option<Single<(Entity, &mut FeaturePacks)>>,
+) {
+    if sandbox::ENABLED {
+        return;
+    }
+
+    for entity in selection_entities.iter().chain(skip.iter()) {
+        commands.entity(entity).despawn();
+        //.insert_recursive::<Children>(Disabled);
+    }
+
+    if let Some(packs) = packs {
+        let (entity, mut packs) = packs.into_inner();
+        if packs.finish_pack() {
+            commands.set_state(SelectionState::SpawnSelection);
+        } else {
+            commands.set_state(GameState::Playing);
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[derive(Component)]
+struct SlotHighlight;
+
+/// Empty slots take the focus while a selected feature waits to be placed.
+fn focus_slots(
+    mut commands: Commands,
+    slots: Query<(Entity, Has<SlotFeature>, Has<Focusable>, Has<Focused>), With<FeatureSlot>>,
+    highlights: Query<(Entity, &ChildOf), With<SlotHighlight>>,
+    selected_feature: Option<Single<(), With<SelectedFeature>>>,
+) {
+    let placing = selected_feature.is_some();
+    for (slot, occupied, focusable, focused) in slots.iter() {
+        match (placing && !occupied, focusable) {
+            (true, false) => {
+                commands.entity(slot).insert(Focusable::default());
+            }
+            (false, true) => {
+                commands.entity(slot).remove::<Focusable>();
+            }
+            _ => {}
+        }
+
+        let highlight = highlights
+            .iter()
+            .find(|(_, child_of)| child_of.parent() == slot);
+        match (focused, highlight) {
+            (true, None) => {
+                let color: Color = POINT_COLOR.into();
+                commands.entity(slot).with_child((
+                    SlotHighlight,
+                    Sprite::from_color(color.with_alpha(0.6), Vec2::splat(14.)),
+                    Transform::from_translation(Vec3::NEG_Z),
+                ));
+            }
+            (false, Some((entity, _))) => {
+                commands.entity(entity).despawn();
+            }
+            _ => {}
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct SelectedFeature(FeatureSpawner);
 
@@ -393,42 +479,55 @@ fn spawn_feature(
     selected_feature: Single<(Entity, &SelectedFeature)>,
 
     input: Res<ButtonInput<MouseButton>>,
+    mut taps: Taps,
+    mut activations: EventReader<Activate>,
     window: Single<&Window, With<PrimaryWindow>>,
     camera: Single<(&Camera, &GlobalTransform), With<OuterCamera>>,
 
-    packs: Option<Single<(Entity, &FeaturePacks)>>,
+    packs: Option<Single<(Entity, &mut FeaturePacks)>>,
 ) {
     let (camera, gt) = camera.into_inner();
-    if !input.just_pressed(MouseButton::Left) {
-        return;
-    }
-
-    let Some(world_position) = window
-        .cursor_position()
+    let tap = taps.read().last();
+    let click = input
+        .just_pressed(MouseButton::Left)
+        .then(|| window.cursor_position())
+        .flatten()
         .and_then(|cursor| camera.viewport_to_world(gt, cursor).ok())
-        .map(|ray| ray.origin.truncate() / crate::RESOLUTION_SCALE)
-    else {
-        return;
-    };
+        .map(|ray| ray.origin.truncate() / crate::RESOLUTION_SCALE);
 
-    let Some((nearest_slot, transform)) = slots.iter().min_by(|a, b| {
-        let a = world_position.distance_squared(a.1.compute_transform().translation.xy());
-        let b = world_position.distance_squared(b.1.compute_transform().translation.xy());
+    let activated = activations
+        .read()
+        .filter_map(|activate| slots.get(activate.0).ok())
+        .last();
 
-        a.total_cmp(&b)
-    }) else {
-        return;
-    };
+    let nearest_slot = if let Some((slot, _)) = activated {
+        slot
+    } else {
+        let Some(world_position) = tap.or(click) else {
+            return;
+        };
+
+        let Some((nearest_slot, transform)) = slots.iter().min_by(|a, b| {
+            let a = world_position.distance_squared(a.1.compute_transform().translation.xy());
+            let b = world_position.distance_squared(b.1.compute_transform().translation.xy());
+
+            a.total_cmp(&b)
+        }) else {
+            return;
+        };
+
+        if transform
+            .compute_transform()
+            .translation
+            .xy()
+            .distance(world_position)
+            > 50.0
+        {
+            return;
+        }
 
-    if transform
-        .compute_transform()
-        .translation
-        .xy()
-        .distance(world_position)
-        > 50.0
-    {
-        return;
-    }
+        nearest_slot
+    };
 
     let (entity, selected_feature) = selected_feature.into_inner();
     let mut entity_commands = commands.spawn((
@@ -447,12 +546,12 @@ fn spawn_feature(
     }
 
     if let Some(packs) = packs {
-        let (entity, packs) = packs.into_inner();
-        if packs.0.is_empty() {
+        let (entity, mut packs) = packs.into_inner();
+        if packs.finish_pack() {
+            commands.set_state(SelectionState::SpawnSelection);
+        } else {
             commands.set_state(GameState::Playing);
             commands.entity(entity).despawn();
-        } else {
-            commands.set_state(SelectionState::SpawnSelection);
         }
     }
 }
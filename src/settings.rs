@@ -0,0 +1,180 @@
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PrimaryWindow, WindowMode};
+use bevy_persistent::prelude::*;
+
+use crate::accessibility::Palette;
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreStartup, settings)
+            .add_systems(Update, apply_window_mode);
+    }
+}
+
+/// Player preferences, saved to `settings.ron` next to the player data.
+///
+/// Fields missing from the file keep their default, so new settings can be added freely.
+#[derive(Resource, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub fullscreen: bool,
+    pub pause_on_focus_loss: bool,
+    /// Linear gain of each [`Bus`](crate::audio::Bus), from 0 to 1.
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub ui_volume: f32,
+    /// How hard the camera shakes, from 0 for not at all to 1.
+    pub screen_shake: f32,
+    /// Holds flashing text and light strobes on steady colors.
+    pub reduced_flashing: bool,
+    pub palette: Palette,
+    /// Size of tooltip and HUD text relative to its normal size.
+    pub text_scale: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            fullscreen: false,
+            pause_on_focus_loss: true,
+            master_volume: 1.,
+            music_volume: 1.,
+            sfx_volume: 1.,
+            ui_volume: 1.,
+            screen_shake: 1.,
+            reduced_flashing: false,
+            palette: Palette::Standard,
+            text_scale: 1.,
+        }
+    }
+}
+
+/// Text scale limits, in tenths.
+const MIN_TEXT_SCALE: i32 = 8;
+const MAX_TEXT_SCALE: i32 = 16;
+
+/// Every setting, in the order they are listed on the settings screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    UiVolume,
+    ScreenShake,
+    ReducedFlashing,
+    Palette,
+    TextScale,
+    Fullscreen,
+    PauseOnFocusLoss,
+}
+
+impl Setting {
+    pub const ALL: [Self; 10] = [
+        Self::MasterVolume,
+        Self::MusicVolume,
+        Self::SfxVolume,
+        Self::UiVolume,
+        Self::ScreenShake,
+        Self::ReducedFlashing,
+        Self::Palette,
+        Self::TextScale,
+        Self::Fullscreen,
+        Self::PauseOnFocusLoss,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::MasterVolume => "Master volume",
+            Self::MusicVolume => "Music volume",
+            Self::SfxVolume => "Effects volume",
+            Self::UiVolume => "Interface volume",
+            Self::ScreenShake => "Screen shake",
+            Self::ReducedFlashing => "Reduced flashing",
+            Self::Palette => "Color palette",
+            Self::TextScale => "Text size",
+            Self::Fullscreen => "Fullscreen",
+            Self::PauseOnFocusLoss => "Pause when unfocused",
+        }
+    }
+
+    pub fn value(&self, settings: &Settings) -> String {
+        let on_off = |on: bool| String::from(if on { "ON" } else { "OFF" });
+        let percent = |fraction: f32| format!("{:.0}%", fraction * 100.);
+        match self {
+            Self::MasterVolume => percent(settings.master_volume),
+            Self::MusicVolume => percent(settings.music_volume),
+            Self::SfxVolume => percent(settings.sfx_volume),
+            Self::UiVolume => percent(settings.ui_volume),
+            Self::ScreenShake => percent(settings.screen_shake),
+            Self::ReducedFlashing => on_off(settings.reduced_flashing),
+            Self::Palette => settings.palette.label().into(),
+            Self::TextScale => percent(settings.text_scale),
+            Self::Fullscreen => on_off(settings.fullscreen),
+            Self::PauseOnFocusLoss => on_off(settings.pause_on_focus_loss),
+        }
+    }
+
+    /// Steps the setting up or down, or cycles it with a `step` of 0. Toggles flip either way.
+    pub fn adjust(&self, settings: &mut Settings, step: i32) {
+        let tenths = |fraction: &mut f32| {
+            let tenths = (*fraction * 10.).round() as i32;
+            let next = match step {
+                0 => (tenths + 1) % 11,
+                _ => (tenths + step).clamp(0, 10),
+            };
+            *fraction = next as f32 / 10.;
+        };
+
+        match self {
+            Self::MasterVolume => tenths(&mut settings.master_volume),
+            Self::MusicVolume => tenths(&mut settings.music_volume),
+            Self::SfxVolume => tenths(&mut settings.sfx_volume),
+            Self::UiVolume => tenths(&mut settings.ui_volume),
+            Self::ScreenShake => tenths(&mut settings.screen_shake),
+            Self::ReducedFlashing => settings.reduced_flashing = !settings.reduced_flashing,
+            Self::Palette => settings.palette = settings.palette.cycle(step),
+            Self::TextScale => {
+                let tenths = (settings.text_scale * 10.).round() as i32;
+                let next = match step {
+                    0 if tenths >= MAX_TEXT_SCALE => MIN_TEXT_SCALE,
+                    0 => tenths + 1,
+                    _ => (tenths + step).clamp(MIN_TEXT_SCALE, MAX_TEXT_SCALE),
+                };
+                settings.text_scale = next as f32 / 10.;
+            }
+            Self::Fullscreen => settings.fullscreen = !settings.fullscreen,
+            Self::PauseOnFocusLoss => settings.pause_on_focus_loss = !settings.pause_on_focus_loss,
+        }
+    }
+}
+
+fn settings(mut commands: Commands) {
+    commands.insert_resource(
+        Persistent::<Settings>::builder()
+            .name("settings")
+            .format(StorageFormat::Ron)
+            .path(crate::save::save_dir().join("settings.ron"))
+            .default(Default::default())
+            .revert_to_default_on_deserialization_errors(true)
+            .build()
+            .unwrap(),
+    )
+}
+
+fn apply_window_mode(
+    settings: Res<Persistent<Settings>>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    window.mode = if settings.fullscreen {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    };
+}
@@ -0,0 +1,95 @@
+use bevy::math::FloatExt;
+use bevy::prelude::*;
+use bevy_optix::camera::MainCamera;
+use bevy_optix::pixel_perfect::OuterCamera;
+use bevy_persistent::prelude::*;
+
+use crate::settings::Settings;
+
+pub struct ShakePlugin;
+
+impl Plugin for ShakePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Trauma>()
+            .init_resource::<Shake>()
+            .add_systems(
+                PostUpdate,
+                shake_cameras.before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// Adds trauma to the camera, from 0 for nothing to 1 for the biggest impacts.
+///
+/// Trauma adds up and is capped at 1, so many small hits shake like one big one.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct Trauma(pub f32);
+
+#[derive(Default, Resource)]
+struct Shake {
+    trauma: f32,
+    /// What the cameras were last moved by, undone before the next shake.
+    offset: Vec2,
+    angle: f32,
+}
+
+/// Trauma lost per second.
+const DECAY: f32 = 1.5;
+/// Offset, in canvas pixels, and rotation, in radians, at full trauma.
+const MAX_OFFSET: f32 = 12.;
+const MAX_ANGLE: f32 = 0.03;
+/// How quickly the shake wanders, in noise cells per second.
+const FREQUENCY: f32 = 18.;
+
+/// Moves the low resolution camera by whole pixels and turns the upscaled one, so the
+/// canvas never samples between pixels.
+///
+/// Runs on real time to keep shaking through hit-stops and settle while paused.
+fn shake_cameras(
+    time: Res<Time<Real>>,
+    mut shake: ResMut<Shake>,
+    mut reader: EventReader<Trauma>,
+    settings: Res<Persistent<Settings>>,
+    mut main: Single<&mut Transform, (With<MainCamera>, Without<OuterCamera>)>,
+    mut outer: Single<&mut Transform, With<OuterCamera>>,
+) {
+    for Trauma(trauma) in reader.read() {
+        shake.trauma = (shake.trauma + trauma).min(1.);
+    }
+    shake.trauma = (shake.trauma - DECAY * time.delta_secs()).max(0.);
+
+    let amount = shake.trauma * shake.trauma * settings.screen_shake;
+    let t = time.elapsed_secs() * FREQUENCY;
+    let offset =
+        (Vec2::new(noise(0, t), noise(1, t)) * MAX_OFFSET * amount * crate::RESOLUTION_SCALE)
+            .round()
+            / crate::RESOLUTION_SCALE;
+    let angle = noise(2, t) * MAX_ANGLE * amount;
+
+    if offset != shake.offset {
+        main.translation += (offset - shake.offset).extend(0.);
+        shake.offset = offset;
+    }
+    if angle != shake.angle {
+        outer.rotate_z(angle - shake.angle);
+        shake.angle = angle;
+    }
+}
+
+/// Smooth noise from -1 to 1, a different curve for each `seed`.
+fn noise(seed: u32, t: f32) -> f32 {
+    let cell = t.floor();
+    let f = t - cell;
+    let f = f * f * (3. - 2. * f);
+    hash(seed, cell as i32).lerp(hash(seed, cell as i32 + 1), f)
+}
+
+fn hash(seed: u32, cell: i32) -> f32 {
+    let mut x = (cell as u32).wrapping_mul(0x9e37_79b9) ^ seed.wrapping_mul(0x85eb_ca6b);
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^= x >> 16;
+    x as f32 / u32::MAX as f32 * 2. - 1.
+}
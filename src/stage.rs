@@ -2,13 +2,15 @@ use avian2d::math::PI;
 use avian2d::prelude::LinearVelocity;
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
-use bevy_seedling::prelude::*;
 use dashu::ibig;
 
+use crate::audio::PlaySound;
 use crate::ball::{BallComponents, PlayerBall};
 use crate::big::BigPoints;
 use crate::collectables::{Money, MoneyEvent, Points};
+use crate::hitstop::HitStop;
 use crate::sandbox;
+use crate::shake::Trauma;
 use crate::state::{GameState, Playing, StateAppExt, remove_entities};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
@@ -16,6 +18,14 @@ pub struct StageSet;
 
 pub struct StagePlugin;
 
+/// Camera trauma for losing a ball, clearing a stage and ending a run.
+const DRAIN_TRAUMA: f32 = 0.5;
+const ADVANCE_TRAUMA: f32 = 0.4;
+const END_TRAUMA: f32 = 0.6;
+
+/// Height below which the last ball of the last life is about to drain.
+const LAST_BALL_Y: f32 = -crate::HEIGHT / 2. + 80.;
+
 impl Plugin for StagePlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<SpawnPosition>()
@@ -32,7 +42,8 @@ impl Plugin for StagePlugin {
             .configure_sets(PreUpdate, StageSet.in_set(Playing));
 
         if !sandbox::ENABLED {
-            app.add_systems(PreUpdate, stage.in_set(StageSet));
+            app.add_systems(PreUpdate, stage.in_set(StageSet))
+                .add_systems(Update, (last_ball_stop, target_stop).in_set(Playing));
         }
 
         #[cfg(debug_assertions)]
@@ -72,7 +83,7 @@ fn spawn_stage(mut commands: Commands) {
         .observe(advance);
 }
 
-#[derive(Debug, Clone, Component)]
+#[derive(Debug, Clone, Component, serde::Serialize, serde::Deserialize)]
 pub struct Stage {
     pub points: BigPoints,
     pub level: usize,
@@ -131,7 +142,8 @@ pub struct SpawnPosition(pub Vec2);
 
 fn stage(
     mut commands: Commands,
-    server: Res<AssetServer>,
+    mut sounds: EventWriter<PlaySound>,
+    mut shake: EventWriter<Trauma>,
     points: Res<Points>,
     alive: Query<&BallComponents>,
     stage: Single<(Entity, &mut Stage)>,
@@ -148,10 +160,8 @@ fn stage(
                 LinearVelocity(Vec2::from_angle(PI * 0.72) * 800.0),
             ));
 
-            commands.spawn(
-                SamplePlayer::new(server.load("audio/pinball/1BootUp.ogg"))
-                    .with_volume(Volume::Linear(0.5)),
-            );
+            sounds.write(PlaySound::new("ball.drain"));
+            shake.write(Trauma(DRAIN_TRAUMA));
         } else {
             let mut entity = commands.entity(entity);
             if stage.progress(points.get().clone()) {
@@ -167,24 +177,67 @@ fn stage(
     }
 }
 
-fn win(trigger: Trigger<OnAdd, Win>, mut commands: Commands, server: Res<AssetServer>) {
+/// Whether a ball, the only one left in play, is falling towards the drain with no
+/// lives left behind it.
+pub fn last_ball_falling(stage: &Stage, transform: &Transform, velocity: &LinearVelocity) -> bool {
+    stage.lives == 0 && transform.translation.y < LAST_BALL_Y && velocity.y < 0.
+}
+
+/// Slows play as the very last ball falls towards the drain.
+fn last_ball_stop(
+    mut stops: EventWriter<HitStop>,
+    balls: Query<(Entity, &Transform, &LinearVelocity), With<BallComponents>>,
+    stage: Single<&Stage>,
+    mut slowed: Local<Option<Entity>>,
+) {
+    let Ok((entity, transform, velocity)) = balls.single() else {
+        return;
+    };
+
+    if last_ball_falling(&stage, transform, velocity) && *slowed != Some(entity) {
+        *slowed = Some(entity);
+        stops.write(HitStop::slow(0.3, 600));
+    }
+}
+
+/// Freezes play for a beat when the points first reach the stage target.
+fn target_stop(
+    mut stops: EventWriter<HitStop>,
+    points: Res<Points>,
+    stage: Single<&Stage>,
+    mut reached: Local<bool>,
+) {
+    let reaching = points.get().0 >= stage.points.0;
+    if reaching && !*reached {
+        stops.write(HitStop::freeze(120));
+    }
+    *reached = reaching;
+}
+
+fn win(
+    trigger: Trigger<OnAdd, Win>,
+    mut commands: Commands,
+    mut sounds: EventWriter<PlaySound>,
+    mut shake: EventWriter<Trauma>,
+) {
     commands.entity(trigger.target()).remove::<Win>();
 
     commands.set_state(GameState::Reset);
-    commands.spawn(
-        SamplePlayer::new(server.load("audio/pinball/1JACKPOT.ogg"))
-            .with_volume(Volume::Linear(0.5)),
-    );
+    sounds.write(PlaySound::new("stage.win"));
+    shake.write(Trauma(END_TRAUMA));
 }
 
-fn loose(trigger: Trigger<OnAdd, Loose>, mut commands: Commands, server: Res<AssetServer>) {
+fn loose(
+    trigger: Trigger<OnAdd, Loose>,
+    mut commands: Commands,
+    mut sounds: EventWriter<PlaySound>,
+    mut shake: EventWriter<Trauma>,
+) {
     commands.entity(trigger.target()).remove::<Loose>();
 
     commands.set_state(GameState::Reset);
-    commands.spawn(
-        SamplePlayer::new(server.load("audio/pinball/1destroyed.ogg"))
-            .with_volume(Volume::Linear(0.5)),
-    );
+    sounds.write(PlaySound::new("stage.lose"));
+    shake.write(Trauma(END_TRAUMA));
 }
 
 #[derive(Event)]
@@ -196,7 +249,8 @@ pub struct AdvanceEvent {
 fn advance(
     trigger: Trigger<OnAdd, Advance>,
     mut commands: Commands,
-    server: Res<AssetServer>,
+    mut sounds: EventWriter<PlaySound>,
+    mut shake: EventWriter<Trauma>,
     mut points: ResMut<Points>,
     stage: Single<&Stage>,
     mut writer: EventWriter<AdvanceEvent>,
@@ -208,10 +262,8 @@ fn advance(
         points: points.get().clone(),
         level: stage.level - 1,
     });
-    commands.spawn(
-        SamplePlayer::new(server.load("audio/pinball/1JACKPOT.ogg"))
-            .with_volume(Volume::Linear(0.5)),
-    );
+    sounds.write(PlaySound::new("stage.advance"));
+    shake.write(Trauma(ADVANCE_TRAUMA));
     points.reset();
     money.write(MoneyEvent {
         money: 1,
@@ -2,50 +2,41 @@ use bevy::ecs::query::QueryFilter;
 use bevy::ecs::system::ScheduleSystem;
 use bevy::prelude::*;
 
-use crate::cabinet::TransitionFinished;
+use crate::sandbox;
+use crate::save::ResumeRun;
 use crate::selection::{FeaturePack, SelectionEvent};
-use crate::{cabinet, sandbox};
+use crate::transition::{GoTo, Transition, TransitionFinished};
 
 pub struct StatePlugin;
 
 impl Plugin for StatePlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<GameState>()
+            .init_resource::<ResetTarget>()
             .add_systems(OnEnter(GameState::Reset), reset)
-            .add_systems(OnEnter(GameState::StartGame), start)
-            .add_systems(OnEnter(GameState::ToGame), cabinet::transition)
-            .add_systems(OnEnter(GameState::ToLeaderboard), cabinet::transition)
             .add_systems(
-                Update,
-                (
-                    enter_game.in_set(ToGame),
-                    enter_leaderboard.in_set(ToLeaderboard),
-                ),
+                OnExit(GameState::Reset),
+                insert_resource(ResetTarget::default()),
             )
+            .add_systems(OnEnter(GameState::StartGame), start)
+            .add_systems(Update, finish_transition)
             .state_variant::<Loading, _>(GameState::Loading)
             .state_variant::<Menu, _>(GameState::Menu)
             .state_variant::<StartGame, _>(GameState::StartGame)
             .state_variant::<Playing, _>(GameState::Playing)
             .state_variant::<Leaderboard, _>(GameState::Leaderboard)
+            .state_variant::<Initials, _>(GameState::Initials)
+            .state_variant::<Achievements, _>(GameState::Achievements)
+            .state_variant::<Controls, _>(GameState::Controls)
             .state_variant::<Selection, _>(GameState::Selection)
             .state_variant::<Reset, _>(GameState::Reset)
-            .state_variant::<ToGame, _>(GameState::ToGame)
-            .state_variant::<ToLeaderboard, _>(GameState::ToLeaderboard);
-
-        #[cfg(debug_assertions)]
-        app.add_systems(Update, state);
+            .add_observer(reset_run);
     }
 }
 
-fn enter_game(mut commands: Commands, mut reader: EventReader<TransitionFinished>) {
-    for _ in reader.read() {
-        commands.set_state(GameState::StartGame);
-    }
-}
-
-fn enter_leaderboard(mut commands: Commands, mut reader: EventReader<TransitionFinished>) {
-    for _ in reader.read() {
-        commands.set_state(GameState::Leaderboard);
+fn finish_transition(mut commands: Commands, mut reader: EventReader<TransitionFinished>) {
+    for finished in reader.read() {
+        commands.set_state(finished.state.clone());
     }
 }
 
@@ -57,10 +48,41 @@ pub enum GameState {
     StartGame,
     Playing,
     Leaderboard,
+    Initials,
+    Achievements,
+    Controls,
     Selection,
     Reset,
-    ToGame,
-    ToLeaderboard,
+}
+
+/// Where [`GameState::Reset`] goes once the run is cleaned up.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+pub enum ResetTarget {
+    /// The run ended, record it.
+    #[default]
+    Leaderboard,
+    /// Start a fresh run straight away.
+    Restart,
+    /// Abandon the run but keep its save, so it can be continued.
+    Menu,
+}
+
+/// The rules a run was played under.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum GameMode {
+    #[default]
+    Standard,
+    Sandbox,
+}
+
+impl GameMode {
+    pub fn current() -> Self {
+        if sandbox::ENABLED {
+            Self::Sandbox
+        } else {
+            Self::Standard
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, SystemSet)]
@@ -79,16 +101,19 @@ pub struct Playing;
 pub struct Leaderboard;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, SystemSet)]
-pub struct Selection;
+pub struct Initials;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, SystemSet)]
-pub struct Reset;
+pub struct Achievements;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, SystemSet)]
-pub struct ToGame;
+pub struct Controls;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, SystemSet)]
-pub struct ToLeaderboard;
+pub struct Selection;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, SystemSet)]
+pub struct Reset;
 
 /// Configure a [`SystemSet`] `V` to run if state is `S` for these schedules:
 ///
@@ -118,18 +143,36 @@ impl StateVariantAppExt for App {
     }
 }
 
-#[cfg(debug_assertions)]
-fn state(mut commands: Commands, input: Res<ButtonInput<KeyCode>>) {
-    if input.just_pressed(KeyCode::KeyR) {
-        commands.set_state(GameState::Reset);
-    }
+/// Ends the run early when [`Control::Reset`](crate::input::Control::Reset) is pressed.
+fn reset_run(
+    _: Trigger<bevy_enhanced_input::events::Fired<crate::input::ResetRun>>,
+    mut commands: Commands,
+) {
+    commands.set_state(GameState::Reset);
 }
 
-fn reset(mut commands: Commands) {
-    commands.set_state(GameState::ToLeaderboard);
+fn reset(mut commands: Commands, mut writer: EventWriter<GoTo>, target: Res<ResetTarget>) {
+    match *target {
+        ResetTarget::Leaderboard => {
+            writer.write(GoTo::new(GameState::Initials, Transition::iris()));
+        }
+        ResetTarget::Restart => {
+            writer.write(GoTo::new(GameState::StartGame, Transition::slider()));
+        }
+        ResetTarget::Menu => commands.set_state(GameState::Menu),
+    }
 }
 
-fn start(mut commands: Commands, mut writer: EventWriter<SelectionEvent>) {
+fn start(
+    mut commands: Commands,
+    mut writer: EventWriter<SelectionEvent>,
+    resume: Option<Res<ResumeRun>>,
+) {
+    if resume.is_some() {
+        // `save::restore_run` picks up from here.
+        return;
+    }
+
     if !sandbox::ENABLED {
         writer.write(SelectionEvent {
             packs: FeaturePack::triple_starter(),
@@ -1,13 +1,11 @@
-use avian2d::prelude::{LinearVelocity, RigidBody};
 use bevy::ecs::component::HookContext;
 use bevy::ecs::world::DeferredWorld;
 use bevy::prelude::*;
-use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
+use bevy_persistent::prelude::*;
 use bevy_tween::prelude::*;
 use bevy_tween::tween::apply_component_tween_system;
-use rand::Rng;
 
-use crate::tween::{DespawnTweenFinish, linear_velocity};
+use crate::settings::Settings;
 
 pub struct TextPlugin;
 
@@ -79,8 +77,17 @@ fn update_text_flash(
         Option<&TextAlpha>,
     )>,
     time: Res<Time>,
+    settings: Res<Persistent<Settings>>,
 ) {
     for (entity, text, mut color, timer, alpha) in text.iter_mut() {
+        if settings.reduced_flashing {
+            let steady = text.normal.with_alpha(color.0.alpha());
+            if color.0 != steady {
+                color.0 = steady;
+            }
+            continue;
+        }
+
         let Some(mut timer) = timer else {
             commands
                 .entity(entity)
@@ -154,52 +161,3 @@ fn update_text_flash(
 //        )
 //        .insert(DespawnTweenFinish);
 //}
-
-pub fn flash_text_rotate(
-    commands: &mut Commands,
-    server: &AssetServer,
-    text: impl Into<String>,
-    size: f32,
-    position: Vec3,
-    rotation: f32,
-    color: impl Into<Color>,
-) {
-    let text = commands
-        .spawn((
-            HIGH_RES_LAYER,
-            Text2d::new(text.into()),
-            TextFont {
-                font: server.load("fonts/cube.ttf"),
-                font_size: size,
-                ..Default::default()
-            },
-            Transform::from_translation(position).with_rotation(Quat::from_rotation_z(rotation)),
-            TextFlash::new(0.1, Color::WHITE, color),
-            RigidBody::Kinematic,
-            LinearVelocity::default(),
-        ))
-        .id();
-
-    let dur = 0.75 * 2.;
-
-    let mut rng = rand::thread_rng();
-    let dir = Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)).normalize();
-    commands
-        .entity(text)
-        .animation()
-        .insert_tween_here(
-            Duration::from_secs_f32(dur),
-            EaseKind::QuarticIn,
-            text.into_target()
-                .with(linear_velocity(dir * 20., Vec2::ZERO)),
-        )
-        .insert(DespawnTweenFinish);
-    commands
-        .animation()
-        .insert_tween_here(
-            Duration::from_secs_f32(dur),
-            EaseKind::QuarticIn,
-            text.into_target().with(text_alpha(1., 0.)),
-        )
-        .insert(DespawnTweenFinish);
-}
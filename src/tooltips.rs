@@ -1,6 +1,8 @@
 use std::time::Duration;
 
+use crate::accessibility::ScaledText;
 use crate::feature::Price;
+use crate::focus::Focused;
 use avian2d::prelude::*;
 use bevy::ecs::component::HookContext;
 use bevy::ecs::world::DeferredWorld;
@@ -17,10 +19,13 @@ use convert_case::{Case, Casing};
 
 pub struct TooltipPlugin;
 
+/// Bevy's default font size, which the price and description are shown at.
+const TOOLTIP_TEXT_SIZE: f32 = 20.;
+
 impl Plugin for TooltipPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(MeshPickingPlugin)
-            .add_systems(Update, (hover, show_tooltips_after));
+            .add_systems(Update, (hover, show_tooltips_after, focus_cards));
     }
 }
 
@@ -170,6 +175,7 @@ fn spawn_tooltips(
 ) -> Entity {
     let sprite = commands
         .spawn((
+            Card,
             HIGH_RES_LAYER,
             Sprite {
                 image: server.load("textures/feature_card.png"),
@@ -198,18 +204,21 @@ fn spawn_tooltips(
                         font: server.load("fonts/saiba.ttf"),
                         ..Default::default()
                     },
+                    ScaledText(25.),
                     TextBounds::new_horizontal(220.),
                     TextLayout::new_with_justify(JustifyText::Center),
                     Transform::from_xyz(0., -100., 0.),
                 ),
                 (
                     Text2d::new(price),
+                    ScaledText(TOOLTIP_TEXT_SIZE),
                     TextBounds::new_horizontal(220.),
                     TextLayout::new_with_justify(JustifyText::Center),
                     Transform::from_xyz(0., -150., 0.),
                 ),
                 (
                     Text2d::new(tips.desc),
+                    ScaledText(TOOLTIP_TEXT_SIZE),
                     TextBounds::new_horizontal(220.),
                     TextLayout::new_with_justify(JustifyText::Center),
                     Transform::from_xyz(0., -250., 0.),
@@ -220,9 +229,16 @@ fn spawn_tooltips(
         .id()
 }
 
+#[derive(Component)]
+struct Card;
+
 #[derive(Component)]
 pub struct Hover;
 
+/// The [`Hover`] highlight comes from the navigation focus rather than the cursor.
+#[derive(Component)]
+struct FocusHover;
+
 #[derive(Component)]
 struct HoverChild;
 
@@ -236,19 +252,7 @@ fn hover_sprite(
         return;
     }
 
-    commands
-        .entity(trigger.event().target)
-        .insert(Hover)
-        .with_child((
-            HoverChild,
-            HIGH_RES_LAYER,
-            Sprite {
-                image: server.load("textures/feature_card_hover.png"),
-                anchor: Anchor::TopCenter,
-                ..Default::default()
-            },
-            Transform::from_translation(Vec3::Z),
-        ));
+    highlight_card(&mut commands, &server, trigger.event().target);
 }
 
 fn remove_hover_sprite(
@@ -257,11 +261,56 @@ fn remove_hover_sprite(
     cards: Query<&Children, With<Hover>>,
     hovers: Query<Entity, With<HoverChild>>,
 ) {
-    let Ok(children) = cards.get(trigger.target()) else {
-        return;
-    };
+    if let Ok(children) = cards.get(trigger.target()) {
+        clear_card(&mut commands, trigger.target(), children, &hovers);
+    }
+}
+
+/// Highlights the cards of the feature that has the [`Focused`] navigation focus.
+fn focus_cards(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    cards: Query<(Entity, Option<&Children>, Has<Hover>, Has<FocusHover>), With<Card>>,
+    child_ofs: Query<&ChildOf>,
+    focused: Query<(), With<Focused>>,
+    hovers: Query<Entity, With<HoverChild>>,
+) {
+    for (card, children, hover, focus_hover) in cards.iter() {
+        let is_focused = child_ofs
+            .iter_ancestors(card)
+            .any(|entity| focused.contains(entity));
+
+        if is_focused && !hover {
+            highlight_card(&mut commands, &server, card);
+            commands.entity(card).insert(FocusHover);
+        } else if !is_focused && focus_hover {
+            if let Some(children) = children {
+                clear_card(&mut commands, card, children, &hovers);
+            }
+        }
+    }
+}
 
-    commands.entity(trigger.target()).remove::<Hover>();
+fn highlight_card(commands: &mut Commands, server: &AssetServer, card: Entity) {
+    commands.entity(card).insert(Hover).with_child((
+        HoverChild,
+        HIGH_RES_LAYER,
+        Sprite {
+            image: server.load("textures/feature_card_hover.png"),
+            anchor: Anchor::TopCenter,
+            ..Default::default()
+        },
+        Transform::from_translation(Vec3::Z),
+    ));
+}
+
+fn clear_card(
+    commands: &mut Commands,
+    card: Entity,
+    children: &Children,
+    hovers: &Query<Entity, With<HoverChild>>,
+) {
+    commands.entity(card).remove::<(Hover, FocusHover)>();
     for entity in hovers.iter_many(children.iter()) {
         commands.entity(entity).despawn();
     }
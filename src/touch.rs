@@ -0,0 +1,112 @@
+use bevy::diagnostic::FrameCount;
+use bevy::ecs::system::SystemParam;
+use bevy::input::InputSystem;
+use bevy::input::touch::Touch;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_enhanced_input::prelude::Actions;
+use bevy_optix::pixel_perfect::OuterCamera;
+
+use crate::input::{ActivePlay, Menu};
+use crate::paddle::{FlipPaddles, PaddleSide};
+
+pub struct TouchPlugin;
+
+impl Plugin for TouchPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Tap>()
+            .add_systems(PreUpdate, (taps, touch_paddles).after(InputSystem));
+    }
+}
+
+/// A touch that just landed, in world space.
+///
+/// Written while a [`Menu`] context is active or no context is, which covers the
+/// menus and the feature selection. During play touches drive the paddles instead.
+/// Read them through [`Taps`].
+#[derive(Event)]
+pub struct Tap {
+    position: Vec2,
+    /// The frame the touch landed on.
+    frame: u32,
+}
+
+/// Reads the [`Tap`]s of this frame only.
+///
+/// Events outlive the frame they were written on, so a screen that a tap just opened would
+/// otherwise see that same tap again on its first frame.
+#[derive(SystemParam)]
+pub struct Taps<'w, 's> {
+    reader: EventReader<'w, 's, Tap>,
+    frame: Res<'w, FrameCount>,
+}
+
+impl Taps<'_, '_> {
+    pub fn read(&mut self) -> impl Iterator<Item = Vec2> + '_ {
+        let frame = self.frame.0;
+        self.reader
+            .read()
+            .filter(move |tap| tap.frame == frame)
+            .map(|tap| tap.position)
+    }
+}
+
+fn taps(
+    touches: Res<Touches>,
+    camera: Single<(&Camera, &GlobalTransform), With<OuterCamera>>,
+    active: Option<Single<(), With<Actions<ActivePlay>>>>,
+    menu: Option<Single<(), With<Actions<Menu>>>>,
+    frame: Res<FrameCount>,
+    mut writer: EventWriter<Tap>,
+) {
+    if active.is_some() && menu.is_none() {
+        return;
+    }
+
+    let (camera, gt) = camera.into_inner();
+    for touch in touches.iter_just_pressed() {
+        if let Some(position) = world_position(camera, gt, touch) {
+            writer.write(Tap {
+                position,
+                frame: frame.0,
+            });
+        }
+    }
+}
+
+/// Touching either half of the screen holds the paddle on that side, for as long as any
+/// finger stays down on that half.
+fn touch_paddles(
+    mut commands: Commands,
+    touches: Res<Touches>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    _active: Single<(), With<Actions<ActivePlay>>>,
+    mut was_held: Local<[bool; 2]>,
+) {
+    let mut held = [false; 2];
+    for touch in touches.iter() {
+        let right = touch.start_position().x > window.width() / 2.;
+        held[right as usize] = true;
+    }
+
+    for (index, side) in [PaddleSide::Left, PaddleSide::Right]
+        .into_iter()
+        .enumerate()
+    {
+        if held[index] != was_held[index] {
+            commands.trigger(FlipPaddles {
+                side: Some(side),
+                up: held[index],
+            });
+        }
+    }
+    *was_held = held;
+}
+
+/// Same mapping as the cursor, touch positions are in logical window pixels too.
+fn world_position(camera: &Camera, gt: &GlobalTransform, touch: &Touch) -> Option<Vec2> {
+    camera
+        .viewport_to_world(gt, touch.position())
+        .ok()
+        .map(|ray| ray.origin.truncate() / crate::RESOLUTION_SCALE)
+}
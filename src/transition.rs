@@ -0,0 +1,344 @@
+use bevy::math::FloatExt;
+use bevy::math::curve::{Curve, EaseFunction};
+use bevy::prelude::*;
+use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
+use rand::Rng;
+
+use crate::state::GameState;
+
+pub struct TransitionPlugin;
+
+impl Plugin for TransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GoTo>()
+            .add_event::<TransitionFinished>()
+            .add_systems(Update, (go_to, animate_transitions).chain());
+    }
+}
+
+/// Changes to `state` behind a [`Transition`].
+///
+/// The state changes once the screen is covered, when [`TransitionFinished`] is sent.
+/// Requests made while a transition is running wait for it to end, the latest replacing any
+/// already waiting. Requests for the state the game is in, or that a running transition
+/// is still heading to, are dropped.
+#[derive(Debug, Clone, Event)]
+pub struct GoTo {
+    pub state: GameState,
+    pub transition: Transition,
+}
+
+impl GoTo {
+    pub fn new(state: GameState, transition: Transition) -> Self {
+        Self { state, transition }
+    }
+}
+
+/// The screen is covered and the state should change to `state`.
+#[derive(Debug, Clone, Event)]
+pub struct TransitionFinished {
+    pub state: GameState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionEffect {
+    /// Bars slide in from alternating sides and out the other.
+    SliderWipe,
+    Fade,
+    /// A circle closes on the center of the screen and opens again.
+    Iris,
+    /// The screen is covered and uncovered by blocks in random order.
+    PixelDissolve,
+}
+
+/// How a [`GoTo`] covers the screen.
+///
+/// The screen is covered halfway through `duration`, `ease` shapes both halves.
+#[derive(Debug, Clone, Copy)]
+pub struct Transition {
+    pub effect: TransitionEffect,
+    pub duration: f32,
+    pub ease: EaseFunction,
+}
+
+impl Transition {
+    pub fn new(effect: TransitionEffect) -> Self {
+        Self {
+            effect,
+            duration: 1.,
+            ease: EaseFunction::QuadraticInOut,
+        }
+    }
+
+    pub fn slider() -> Self {
+        Self::new(TransitionEffect::SliderWipe).with_ease(EaseFunction::ExponentialOut)
+    }
+
+    pub fn fade() -> Self {
+        Self::new(TransitionEffect::Fade)
+    }
+
+    pub fn iris() -> Self {
+        Self::new(TransitionEffect::Iris).with_ease(EaseFunction::CubicInOut)
+    }
+
+    pub fn dissolve() -> Self {
+        Self::new(TransitionEffect::PixelDissolve).with_ease(EaseFunction::Linear)
+    }
+
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    pub fn with_ease(mut self, ease: EaseFunction) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    /// How much of the screen is covered, from 0 to 1, `progress` of the way through.
+    ///
+    /// The second half plays the first backwards.
+    fn cover(&self, progress: f32) -> f32 {
+        self.ease.sample_clamped(1. - (progress * 2. - 1.).abs())
+    }
+}
+
+/// A running transition, parent to what it draws.
+#[derive(Component)]
+struct ActiveTransition {
+    transition: Transition,
+    state: GameState,
+    timer: Timer,
+    covered: bool,
+}
+
+#[derive(Component)]
+struct Slider {
+    start: Vec3,
+    middle: Vec3,
+    end: Vec3,
+}
+
+#[derive(Component)]
+struct FadeCover;
+
+#[derive(Component)]
+struct IrisCover;
+
+/// One block of a dissolve, shown while the cover is past `threshold`.
+#[derive(Component)]
+struct DissolveBlock {
+    threshold: f32,
+}
+
+/// Length of the quick fades between the menu and the screens it opens.
+pub const MENU_FADE_SECS: f32 = 0.5;
+
+const TRANSITION_Z: f32 = 980.;
+
+const SLIDER_HEIGHT: f32 = 62.;
+const SLIDER_TRAVEL: f32 = 1024.;
+
+/// The iris is a thin ring's hole, scaled from past the corners of the screen down to
+/// nothing.
+const IRIS_INNER: f32 = 1.;
+const IRIS_OUTER: f32 = 4_000.;
+const IRIS_OPEN: f32 = 1_000.;
+const IRIS_CLOSED: f32 = 0.25;
+
+const DISSOLVE_BLOCK: f32 = 50.;
+
+fn go_to(
+    mut commands: Commands,
+    mut reader: EventReader<GoTo>,
+    server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    current: Res<State<GameState>>,
+    active: Query<&ActiveTransition>,
+    mut waiting: Local<Option<GoTo>>,
+) {
+    let request = reader.read().last().filter(|request| {
+        // Once covered, the state may have moved on, as into a reset, and need this again.
+        active
+            .iter()
+            .all(|running| running.covered || running.state != request.state)
+    });
+    if let Some(request) = request {
+        *waiting = Some(request.clone());
+    }
+    if !active.is_empty() {
+        return;
+    }
+    let Some(GoTo { state, transition }) = waiting.take() else {
+        return;
+    };
+    if state == *current.get() {
+        return;
+    }
+
+    let mut parent = commands.spawn((
+        ActiveTransition {
+            transition,
+            state,
+            timer: Timer::from_seconds(transition.duration, TimerMode::Once),
+            covered: false,
+        },
+        Transform::from_xyz(0., 0., TRANSITION_Z),
+        Visibility::Visible,
+    ));
+
+    match transition.effect {
+        TransitionEffect::SliderWipe => {
+            parent.with_children(|parent| {
+                for row in 0..(SLIDER_TRAVEL / SLIDER_HEIGHT) as usize {
+                    let y = row as f32 * SLIDER_HEIGHT - crate::HEIGHT / 2.;
+                    let left = Vec3::new(-crate::WIDTH / 2. - SLIDER_TRAVEL, y, 0.);
+                    let right = Vec3::new(crate::WIDTH / 2. + SLIDER_TRAVEL, y, 0.);
+                    let (start, end) = if row % 2 == 0 {
+                        (left, right)
+                    } else {
+                        (right, left)
+                    };
+
+                    parent.spawn((
+                        Slider {
+                            start,
+                            middle: Vec3::new(0., y, 0.),
+                            end,
+                        },
+                        HIGH_RES_LAYER,
+                        Transform::from_translation(start)
+                            .with_scale(Vec3::splat(crate::RESOLUTION_SCALE)),
+                        Sprite::from_image(server.load("textures/slider.png")),
+                    ));
+                }
+            });
+        }
+        TransitionEffect::Fade => {
+            parent.with_child((
+                FadeCover,
+                HIGH_RES_LAYER,
+                Sprite::from_color(
+                    Color::BLACK.with_alpha(0.),
+                    Vec2::new(crate::RES_WIDTH * 2., crate::RES_HEIGHT),
+                ),
+            ));
+        }
+        TransitionEffect::Iris => {
+            parent.with_child((
+                IrisCover,
+                HIGH_RES_LAYER,
+                Mesh2d(meshes.add(Annulus::new(IRIS_INNER, IRIS_OUTER).mesh().resolution(128))),
+                MeshMaterial2d(materials.add(Color::BLACK)),
+                Transform::from_scale(Vec3::splat(IRIS_OPEN)),
+            ));
+        }
+        TransitionEffect::PixelDissolve => {
+            let mut rng = rand::thread_rng();
+            let columns = (crate::RES_WIDTH * 1.5 / DISSOLVE_BLOCK).ceil() as i32;
+            let rows = (crate::RES_HEIGHT / DISSOLVE_BLOCK).ceil() as i32;
+            parent.with_children(|parent| {
+                for x in -columns / 2..=columns / 2 {
+                    for y in -rows / 2..=rows / 2 {
+                        parent.spawn((
+                            DissolveBlock {
+                                threshold: rng.gen_range(0.0..1.0),
+                            },
+                            HIGH_RES_LAYER,
+                            Sprite::from_color(Color::BLACK, Vec2::splat(DISSOLVE_BLOCK)),
+                            Transform::from_xyz(
+                                x as f32 * DISSOLVE_BLOCK,
+                                y as f32 * DISSOLVE_BLOCK,
+                                0.,
+                            ),
+                            Visibility::Hidden,
+                        ));
+                    }
+                }
+            });
+        }
+    }
+}
+
+fn animate_transitions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut transitions: Query<(Entity, &mut ActiveTransition, &Children)>,
+    mut sliders: Query<(&Slider, &mut Transform)>,
+    mut fades: Query<&mut Sprite, With<FadeCover>>,
+    mut irises: Query<&mut Transform, (With<IrisCover>, Without<Slider>)>,
+    mut blocks: Query<(&DissolveBlock, &mut Visibility)>,
+    mut writer: EventWriter<TransitionFinished>,
+) {
+    for (entity, mut active, children) in transitions.iter_mut() {
+        active.timer.tick(time.delta());
+        if active.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = active.timer.fraction();
+        if progress >= 0.5 && !active.covered {
+            active.covered = true;
+            writer.write(TransitionFinished {
+                state: active.state.clone(),
+            });
+        }
+
+        let transition = active.transition;
+        let cover = transition.cover(progress);
+        for child in children.iter() {
+            if let Ok((slider, mut transform)) = sliders.get_mut(child) {
+                transform.translation = if progress < 0.5 {
+                    slider.start.lerp(slider.middle, cover)
+                } else {
+                    slider.end.lerp(slider.middle, cover)
+                };
+            }
+
+            if let Ok(mut sprite) = fades.get_mut(child) {
+                sprite.color.set_alpha(cover);
+            }
+
+            if let Ok(mut transform) = irises.get_mut(child) {
+                transform.scale = Vec3::splat(IRIS_OPEN.lerp(IRIS_CLOSED, cover));
+            }
+
+            if let Ok((block, mut visibility)) = blocks.get_mut(child) {
+                *visibility = if block.threshold < cover {
+                    Visibility::Inherited
+                } else {
+                    Visibility::Hidden
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cover_closes_and_opens() {
+        let transition = Transition::new(TransitionEffect::Fade);
+        assert_eq!(transition.cover(0.), 0.);
+        assert_eq!(transition.cover(0.5), 1.);
+        assert_eq!(transition.cover(1.), 0.);
+        for progress in [0.1, 0.25, 0.4] {
+            let cover = transition.cover(progress);
+            assert!(cover > 0. && cover < 1.);
+            assert!((cover - transition.cover(1. - progress)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn cover_is_clamped() {
+        let transition = Transition::slider();
+        assert!(transition.cover(-1.).abs() < 1e-3);
+        assert!(transition.cover(2.).abs() < 1e-3);
+        assert!(transition.cover(0.25) < transition.cover(0.4));
+    }
+}
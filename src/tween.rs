@@ -8,6 +8,7 @@ use bevy_tween::prelude::*;
 use bevy_tween::tween::{apply_component_tween_system, apply_resource_tween_system};
 
 use crate::float_tween;
+use crate::pause::PauseState;
 
 pub struct TweenPlugin;
 
@@ -39,10 +40,11 @@ fn update_time(
     mut virtual_time: ResMut<Time<Virtual>>,
     mut physics_time: ResMut<Time<Physics>>,
     mult: Res<TimeMult>,
+    pause: Res<State<PauseState>>,
 ) {
-    if mult.is_changed() {
-        virtual_time.set_relative_speed(mult.0);
-        physics_time.set_relative_speed(mult.0);
+    if mult.is_changed() || pause.is_changed() {
+        virtual_time.set_relative_speed(pause.scale(mult.0));
+        physics_time.set_relative_speed(pause.scale(mult.0));
     }
 }
 
@@ -54,9 +56,13 @@ float_tween!(
     PhysicsTimeTween
 );
 
-fn update_physics_time(mut time: ResMut<Time<Physics>>, mult: Res<PhysicsTimeMult>) {
-    if mult.is_changed() {
-        time.set_relative_speed(mult.0);
+fn update_physics_time(
+    mut time: ResMut<Time<Physics>>,
+    mult: Res<PhysicsTimeMult>,
+    pause: Res<State<PauseState>>,
+) {
+    if mult.is_changed() || pause.is_changed() {
+        time.set_relative_speed(pause.scale(mult.0));
     }
 }
 
@@ -68,9 +74,13 @@ float_tween!(
     VirtualTimeTween
 );
 
-fn update_virtual_time(mut time: ResMut<Time<Virtual>>, mult: Res<VirtualTimeMult>) {
-    if mult.is_changed() {
-        time.set_relative_speed(mult.0);
+fn update_virtual_time(
+    mut time: ResMut<Time<Virtual>>,
+    mult: Res<VirtualTimeMult>,
+    pause: Res<State<PauseState>>,
+) {
+    if mult.is_changed() || pause.is_changed() {
+        time.set_relative_speed(pause.scale(mult.0));
     }
 }
 